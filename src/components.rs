@@ -1,4 +1,6 @@
+use crate::constants::*;
 use bevy::prelude::*;
+use bevy::tasks::Task;
 use std::collections::HashSet;
 
 #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
@@ -8,6 +10,8 @@ pub enum GameState {
     PetSelection,
     Playing,
     PassiveTree,
+    LevelUp,
+    Paused,
     GameOver,
 }
 
@@ -51,6 +55,15 @@ pub struct Health {
     pub max: f32,
 }
 
+impl Health {
+    /// Spends a fraction of max life; life costs never kill.
+    pub fn pay_life_cost(&mut self, pct: f32) {
+        if pct > 0.0 {
+            self.current = (self.current - self.max * pct).max(1.0_f32.min(self.current));
+        }
+    }
+}
+
 #[derive(Component, Default)]
 pub struct Shield {
     pub amount: f32,
@@ -65,6 +78,216 @@ pub struct Stats {
     pub crit_multiplier: f32,
     pub life_regen: f32,
     pub armor: f32,
+    pub fire_resistance: f32,
+    pub cold_resistance: f32,
+    pub lightning_resistance: f32,
+    pub evasion: f32,
+    pub block_chance: f32,
+    pub block_amount: f32,
+}
+
+/// Stats before passive allocations; `Stats` is rebuilt from this whenever either changes.
+#[derive(Component, Clone, Copy)]
+pub struct BaseStats(pub Stats);
+
+/// Max-life multiplier from passives currently folded into `Health::max`.
+#[derive(Component)]
+pub struct MaxLifeScale(pub f32);
+
+impl Stats {
+    pub fn add_flat(&mut self, other: &Stats) {
+        self.damage += other.damage;
+        self.speed += other.speed;
+        self.attack_speed += other.attack_speed;
+        self.crit_chance += other.crit_chance;
+        self.crit_multiplier += other.crit_multiplier;
+        self.life_regen += other.life_regen;
+        self.armor += other.armor;
+        self.fire_resistance += other.fire_resistance;
+        self.cold_resistance += other.cold_resistance;
+        self.lightning_resistance += other.lightning_resistance;
+        self.evasion += other.evasion;
+        self.block_chance += other.block_chance;
+        self.block_amount += other.block_amount;
+    }
+
+    pub fn evade_chance(&self, bonus: f32) -> f32 {
+        (self.evasion + bonus).clamp(0.0, MAX_EVASION)
+    }
+
+    pub fn capped_block_chance(&self) -> f32 {
+        self.block_chance.clamp(0.0, MAX_BLOCK_CHANCE)
+    }
+
+    pub fn fire_taken(&self) -> f32 {
+        1.0 - self.fire_resistance.clamp(0.0, MAX_RESISTANCE)
+    }
+
+    pub fn cold_taken(&self) -> f32 {
+        1.0 - self.cold_resistance.clamp(0.0, MAX_RESISTANCE)
+    }
+
+    pub fn lightning_taken(&self) -> f32 {
+        1.0 - self.lightning_resistance.clamp(0.0, MAX_RESISTANCE)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageType {
+    Physical,
+    Fire,
+    Cold,
+    Lightning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageSource {
+    Hit,
+    DoT,
+    Explosion,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DefenseOutcome {
+    Evaded,
+    Blocked(f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttackKind {
+    Primary,
+    Skill,
+    Pet,
+    Explosion,
+    Burn,
+    Enemy,
+    Hazard,
+}
+
+impl AttackKind {
+    pub const PLAYER_SOURCES: [AttackKind; 5] = [
+        AttackKind::Primary,
+        AttackKind::Skill,
+        AttackKind::Pet,
+        AttackKind::Explosion,
+        AttackKind::Burn,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AttackKind::Primary => "Primary",
+            AttackKind::Skill => "Skill",
+            AttackKind::Pet => "Pet",
+            AttackKind::Explosion => "Explosion",
+            AttackKind::Burn => "Burn",
+            AttackKind::Enemy => "Enemy",
+            AttackKind::Hazard => "Hazard",
+        }
+    }
+}
+
+/// Level-up offers; separate from the passive tree and applied on top of the baseline growth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Upgrade {
+    Damage,
+    AttackSpeed,
+    MoveSpeed,
+    CritChance,
+    CritMultiplier,
+    LifeRegen,
+    Armor,
+    MaxLife,
+    Evasion,
+    BlockChance,
+    Resistances,
+}
+
+impl Upgrade {
+    pub const ALL: [Upgrade; 11] = [
+        Upgrade::Damage,
+        Upgrade::AttackSpeed,
+        Upgrade::MoveSpeed,
+        Upgrade::CritChance,
+        Upgrade::CritMultiplier,
+        Upgrade::LifeRegen,
+        Upgrade::Armor,
+        Upgrade::MaxLife,
+        Upgrade::Evasion,
+        Upgrade::BlockChance,
+        Upgrade::Resistances,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Upgrade::Damage => "Might",
+            Upgrade::AttackSpeed => "Haste",
+            Upgrade::MoveSpeed => "Swiftness",
+            Upgrade::CritChance => "Keen Eye",
+            Upgrade::CritMultiplier => "Executioner",
+            Upgrade::LifeRegen => "Vitality",
+            Upgrade::Armor => "Plating",
+            Upgrade::MaxLife => "Constitution",
+            Upgrade::Evasion => "Nimble",
+            Upgrade::BlockChance => "Bulwark",
+            Upgrade::Resistances => "Warding",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Upgrade::Damage => "+15% Damage",
+            Upgrade::AttackSpeed => "+10% Attack Speed",
+            Upgrade::MoveSpeed => "+8% Movement Speed",
+            Upgrade::CritChance => "+4% Crit Chance",
+            Upgrade::CritMultiplier => "+0.25x Crit Multiplier",
+            Upgrade::LifeRegen => "+2 Life Regen",
+            Upgrade::Armor => "+15 Armor",
+            Upgrade::MaxLife => "+15% Maximum Life",
+            Upgrade::Evasion => "+5% Evasion",
+            Upgrade::BlockChance => "+5% Block Chance",
+            Upgrade::Resistances => "+8% Elemental Resistances",
+        }
+    }
+
+    /// Draw weight for a class; zero keeps an upgrade out of that class's pool.
+    pub fn weight(&self, class: PlayerClass) -> u32 {
+        match (self, class) {
+            (Upgrade::BlockChance, PlayerClass::Tank) => 3,
+            (Upgrade::BlockChance, _) | (Upgrade::Evasion, PlayerClass::Tank) => 0,
+            (Upgrade::MaxLife | Upgrade::Armor, PlayerClass::Tank)
+            | (
+                Upgrade::AttackSpeed | Upgrade::CritChance | Upgrade::Evasion,
+                PlayerClass::Archer,
+            )
+            | (Upgrade::Damage | Upgrade::CritMultiplier, PlayerClass::Mage)
+            | (Upgrade::LifeRegen | Upgrade::MoveSpeed, PlayerClass::Tamer) => 3,
+            _ => 2,
+        }
+    }
+
+    pub fn apply(&self, stats: &mut Stats, health: &mut Health) {
+        match self {
+            Upgrade::Damage => stats.damage *= 1.15,
+            Upgrade::AttackSpeed => stats.attack_speed *= 1.10,
+            Upgrade::MoveSpeed => stats.speed *= 1.08,
+            Upgrade::CritChance => stats.crit_chance += 0.04,
+            Upgrade::CritMultiplier => stats.crit_multiplier += 0.25,
+            Upgrade::LifeRegen => stats.life_regen += 2.0,
+            Upgrade::Armor => stats.armor += 15.0,
+            Upgrade::MaxLife => {
+                let gain = health.max * 0.15;
+                health.max += gain;
+                health.current += gain;
+            }
+            Upgrade::Evasion => stats.evasion += 0.05,
+            Upgrade::BlockChance => stats.block_chance += 0.05,
+            Upgrade::Resistances => {
+                stats.fire_resistance += 0.08;
+                stats.cold_resistance += 0.08;
+                stats.lightning_resistance += 0.08;
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -73,6 +296,12 @@ pub enum PassiveEffect {
     Ricochet,
     Explosion,
     Knockback,
+    LuckyCrit,
+    Homing,
+    SplitProjectiles,
+    ForkProjectiles,
+    ReturningProjectiles,
+    OrbitingProjectiles,
     ChanceFire(f32),
     ChanceIce(f32),
     ChanceLightning(f32),
@@ -83,9 +312,24 @@ pub enum PassiveEffect {
     ShieldRegen(f32),
     LifeLeech(f32),
     ShieldLeech(f32),
+    BlockChance(f32),
+    Taunt(f32),
+    ProjectileCount(u32),
+    ProjectilePierce(u32),
+    SkillCooldown(f32),
+    AoeSize(f32),
+    PetDamage(f32),
+    PetLife(f32),
+    PetCount(u32),
+    MoreDamage(f32),
+    LessMaxLife(f32),
+    SkillLifeCost(f32),
+    CannotEvade,
+    /// Keystones bundle their upside and drawback effects together.
+    Compound(&'static [PassiveEffect]),
 }
 
-#[derive(Component, Default)]
+#[derive(Component)]
 pub struct ElementalStatus {
     pub fire_stacks: u32,
     pub ice_stacks: u32,
@@ -93,20 +337,179 @@ pub struct ElementalStatus {
     pub is_ignited: bool,
     pub is_frozen: bool,
     pub is_discharged: bool,
+    pub fire_timers: Vec<Timer>,
+    /// Who applied each fire stack, index-aligned with `fire_timers`; `None` for hazards.
+    pub fire_appliers: Vec<Option<Entity>>,
+    pub ice_timers: Vec<Timer>,
+    pub lightning_timers: Vec<Timer>,
+    pub burn_timer: Timer,
+    pub base_color: Option<Color>,
+    pub tinted: bool,
+}
+
+impl Default for ElementalStatus {
+    fn default() -> Self {
+        Self {
+            fire_stacks: 0,
+            ice_stacks: 0,
+            lightning_stacks: 0,
+            is_ignited: false,
+            is_frozen: false,
+            is_discharged: false,
+            fire_timers: Vec::new(),
+            fire_appliers: Vec::new(),
+            ice_timers: Vec::new(),
+            lightning_timers: Vec::new(),
+            burn_timer: Timer::from_seconds(BURN_TICK, TimerMode::Repeating),
+            base_color: None,
+            tinted: false,
+        }
+    }
+}
+
+fn push_stack(timers: &mut Vec<Timer>, duration: f32) {
+    if timers.len() >= MAX_ELEMENTAL_STACKS as usize {
+        timers.remove(0);
+    }
+    timers.push(Timer::from_seconds(duration, TimerMode::Once));
+}
+
+impl ElementalStatus {
+    pub fn add_stack(&mut self, effect: PassiveEffect, applier: Option<Entity>) {
+        match effect {
+            PassiveEffect::ChanceFire(_) => {
+                if self.fire_timers.len() >= MAX_ELEMENTAL_STACKS as usize {
+                    self.fire_appliers.remove(0);
+                }
+                push_stack(&mut self.fire_timers, FIRE_STACK_DURATION);
+                self.fire_appliers.push(applier);
+            }
+            PassiveEffect::ChanceIce(_) => push_stack(&mut self.ice_timers, ICE_STACK_DURATION),
+            PassiveEffect::ChanceLightning(_) => {
+                push_stack(&mut self.lightning_timers, LIGHTNING_STACK_DURATION)
+            }
+            _ => {}
+        }
+        self.sync_stacks();
+    }
+
+    pub fn tick(&mut self, delta: std::time::Duration) {
+        for timers in [
+            &mut self.fire_timers,
+            &mut self.ice_timers,
+            &mut self.lightning_timers,
+        ] {
+            for timer in timers.iter_mut() {
+                timer.tick(delta);
+            }
+        }
+        let mut expired = self.fire_timers.iter().map(Timer::finished);
+        self.fire_appliers
+            .retain(|_| !expired.next().unwrap_or(true));
+        for timers in [
+            &mut self.fire_timers,
+            &mut self.ice_timers,
+            &mut self.lightning_timers,
+        ] {
+            timers.retain(|t| !t.finished());
+        }
+        self.sync_stacks();
+        self.burn_timer.tick(delta);
+
+        if self.fire_stacks == 0 {
+            self.is_ignited = false;
+        }
+        if self.lightning_stacks == 0 {
+            self.is_discharged = false;
+        }
+    }
+
+    /// Fire stack counts grouped by who applied them.
+    pub fn burn_sources(&self) -> Vec<(Option<Entity>, u32)> {
+        let mut sources: Vec<(Option<Entity>, u32)> = Vec::new();
+        for applier in &self.fire_appliers {
+            match sources.iter_mut().find(|(source, _)| source == applier) {
+                Some((_, stacks)) => *stacks += 1,
+                None => sources.push((*applier, 1)),
+            }
+        }
+        sources
+    }
+
+    pub fn discharge(&mut self) {
+        self.is_discharged = true;
+        self.lightning_timers.clear();
+        self.sync_stacks();
+    }
+
+    pub fn thaw(&mut self) {
+        self.is_frozen = false;
+        self.ice_timers.clear();
+        self.sync_stacks();
+    }
+
+    fn sync_stacks(&mut self) {
+        self.fire_stacks = self.fire_timers.len() as u32;
+        self.ice_stacks = self.ice_timers.len() as u32;
+        self.lightning_stacks = self.lightning_timers.len() as u32;
+    }
+
+    pub fn chill_slow(&self) -> f32 {
+        (self.ice_stacks as f32 * CHILL_SLOW_PER_STACK).min(CHILL_MAX_SLOW)
+    }
+
+    pub fn shock_amp(&self) -> f32 {
+        self.lightning_stacks as f32 * SHOCK_DAMAGE_TAKEN_PER_STACK
+    }
 }
 
+#[derive(Component)]
+pub struct Frozen(pub Timer);
+
+#[derive(Component)]
+pub struct DischargeCooldown(pub Timer);
+
+#[derive(Component)]
+pub struct LightningBolt(pub Timer);
+
+#[derive(Component)]
+pub struct EnemyElement(pub PassiveEffect);
+
 #[derive(Component)]
 pub struct Loot;
 
 #[derive(Component)]
 pub struct Boss;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HazardKind {
+    FirePit,
+    IcePatch,
+    LightningPylon,
+}
+
 #[derive(Component)]
 pub struct Hazard {
+    pub kind: HazardKind,
     pub damage: f32,
     pub effect: Option<PassiveEffect>,
+    pub radius: f32,
+    pub tick: Timer,
 }
 
+impl Hazard {
+    pub fn damage_type(&self) -> DamageType {
+        match self.kind {
+            HazardKind::FirePit => DamageType::Fire,
+            HazardKind::IcePatch => DamageType::Cold,
+            HazardKind::LightningPylon => DamageType::Lightning,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct HazardSlow(pub f32);
+
 #[derive(Component)]
 pub struct Wall;
 
@@ -135,6 +538,23 @@ pub enum IconType {
     Boss,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeTier {
+    Minor,
+    Notable,
+    Keystone,
+}
+
+impl NodeTier {
+    pub fn size(&self) -> f32 {
+        match self {
+            NodeTier::Minor => 36.0,
+            NodeTier::Notable => 45.0,
+            NodeTier::Keystone => 58.0,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PassiveNode {
     pub id: u32,
@@ -143,12 +563,19 @@ pub struct PassiveNode {
     pub effect: PassiveEffect,
     pub requirements: Vec<u32>,
     pub position: Vec2,
+    /// Class-exclusive nodes; a class node without requirements is that class's starting root.
+    pub class: Option<PlayerClass>,
+    pub tier: NodeTier,
+    pub drawback: Option<String>,
 }
 
 #[derive(Component, Default)]
 pub struct PlayerPassives {
     pub unlocked_nodes: Vec<u32>,
     pub points: u32,
+    pub respec_charges: u32,
+    /// Queued allocations, applied in order as points become available.
+    pub planned: Vec<u32>,
 }
 
 impl Default for Stats {
@@ -161,6 +588,12 @@ impl Default for Stats {
             crit_multiplier: 2.0,
             life_regen: 2.0,
             armor: 0.0,
+            fire_resistance: 0.0,
+            cold_resistance: 0.0,
+            lightning_resistance: 0.0,
+            evasion: 0.0,
+            block_chance: 0.0,
+            block_amount: 0.0,
         }
     }
 }
@@ -202,9 +635,40 @@ pub struct Projectile {
     pub damage: f32,
     pub owner: Entity,
     pub pierce: u32,
-    pub chain_count: u32,
     pub hit_entities: HashSet<Entity>,
-    pub is_crit: bool,
+    pub crit_bonus: f32,
+    pub kind: AttackKind,
+    pub spent: bool,
+}
+
+#[derive(Component)]
+pub struct Chain(pub u32);
+
+#[derive(Component)]
+pub struct Homing {
+    pub turn_rate: f32,
+    pub range: f32,
+}
+
+#[derive(Component)]
+pub struct SplitOnHit(pub u32);
+
+#[derive(Component)]
+pub struct ForkOnHit;
+
+#[derive(Component)]
+pub struct Returning {
+    pub max_distance: f32,
+    pub travelled: f32,
+    pub returning: bool,
+}
+
+#[derive(Component)]
+pub struct Orbit {
+    pub angle: f32,
+    pub radius: f32,
+    pub angular_speed: f32,
+    pub rehit: Timer,
 }
 
 #[derive(Component)]
@@ -213,21 +677,167 @@ pub struct MeleeAttack {
     pub owner: Entity,
     pub duration: Timer,
     pub hit_entities: HashSet<Entity>,
-    pub is_crit: bool,
+    pub crit_bonus: f32,
+    pub shape: MeleeShape,
+    pub direction: Vec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeleeShape {
+    Arc { radius: f32, half_angle: f32 },
+    Rect { half_extents: Vec2 },
+}
+
+impl MeleeShape {
+    /// `origin` is the arc's apex or the rectangle's center.
+    pub fn contains(&self, origin: Vec2, direction: Vec2, point: Vec2, padding: f32) -> bool {
+        let offset = point - origin;
+        match *self {
+            MeleeShape::Arc { radius, half_angle } => {
+                let distance = offset.length();
+                distance <= radius + padding
+                    && (distance <= padding || direction.angle_between(offset).abs() <= half_angle)
+            }
+            MeleeShape::Rect { half_extents } => {
+                offset.dot(direction).abs() <= half_extents.x + padding
+                    && offset.dot(direction.perp()).abs() <= half_extents.y + padding
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct MeleeCombo {
+    pub step: usize,
+    pub window: Timer,
+}
+
+impl Default for MeleeCombo {
+    fn default() -> Self {
+        Self {
+            step: MELEE_COMBO_STEPS - 1,
+            window: Timer::from_seconds(MELEE_COMBO_WINDOW, TimerMode::Once),
+        }
+    }
+}
+
+impl MeleeCombo {
+    pub fn advance(&mut self) -> usize {
+        self.step = if self.window.finished() {
+            0
+        } else {
+            (self.step + 1) % MELEE_COMBO_STEPS
+        };
+        self.window.reset();
+        self.step
+    }
 }
 
 #[derive(Component)]
 pub struct AoeEffect {
     pub damage: f32,
+    pub damage_type: DamageType,
+    pub crit_bonus: f32,
     pub owner: Entity,
     pub tick_timer: Timer,
     pub duration: Timer,
     pub hit_this_tick: HashSet<Entity>,
+    pub kind: AttackKind,
+}
+
+/// Overrides the default circular AoE footprint taken from the sprite size.
+#[derive(Component)]
+pub struct AoeShape {
+    pub shape: MeleeShape,
+    pub direction: Vec2,
 }
 
+impl AoeShape {
+    /// Footprint for circular AoEs drawn as meshes rather than sized sprites.
+    pub fn circle(radius: f32) -> Self {
+        Self {
+            shape: MeleeShape::Arc {
+                radius,
+                half_angle: std::f32::consts::PI,
+            },
+            direction: Vec2::X,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClassSkill {
+    Meteor,
+    ArrowRain,
+    Whirlwind,
+    SpiritBeam,
+}
+
+impl ClassSkill {
+    pub fn for_class(class: PlayerClass) -> Self {
+        match class {
+            PlayerClass::Mage => ClassSkill::Meteor,
+            PlayerClass::Archer => ClassSkill::ArrowRain,
+            PlayerClass::Tank => ClassSkill::Whirlwind,
+            PlayerClass::Tamer => ClassSkill::SpiritBeam,
+        }
+    }
+
+    pub fn is_channeled(&self) -> bool {
+        matches!(self, ClassSkill::Whirlwind | ClassSkill::SpiritBeam)
+    }
+
+    pub fn cooldown(&self) -> f32 {
+        match self {
+            ClassSkill::Meteor => METEOR_COOLDOWN,
+            ClassSkill::ArrowRain => ARROW_RAIN_COOLDOWN,
+            ClassSkill::Whirlwind => WHIRLWIND_COOLDOWN,
+            ClassSkill::SpiritBeam => SPIRIT_BEAM_COOLDOWN,
+        }
+    }
+}
+
+/// Keeps a channeled AoE attached to its owner while `key` is held.
+#[derive(Component)]
+pub struct Channeled {
+    pub skill: ClassSkill,
+    pub key: KeyCode,
+}
+
+#[derive(Component)]
+pub struct GroundTargetRange;
+
+#[derive(Component)]
+pub struct GroundTargetReticle;
+
 #[derive(Component)]
 pub struct Lifetime(pub Timer);
 
+#[derive(Component)]
+pub struct EnemyWindup {
+    pub timer: Timer,
+    pub direction: Vec2,
+}
+
+#[derive(Component)]
+pub struct EnemyRecovery(pub Timer);
+
+#[derive(Component)]
+pub struct AttackTelegraph {
+    pub owner: Entity,
+    pub full_size: Vec2,
+}
+
+#[derive(Component)]
+pub struct EnemyHitbox {
+    pub damage: f32,
+    pub owner: Entity,
+    pub radius: f32,
+    pub lifetime: Timer,
+    pub hit_entities: HashSet<Entity>,
+    pub effect: Option<PassiveEffect>,
+}
+
 #[derive(Component)]
 pub struct XpOrb {
     pub value: u32,
@@ -256,12 +866,118 @@ pub struct AttackCooldown(pub Timer);
 pub struct SkillCooldowns {
     pub dash: Timer,
     pub nova: Timer,
+    pub special: Timer,
 }
 
+/// Announcements are banner-sized; callouts and notices are progressively smaller labels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatingTextStyle {
+    Damage,
+    Crit,
+    Heal,
+    Shield,
+    Dodge,
+    Announcement,
+    Callout,
+    Notice,
+}
+
+impl FloatingTextStyle {
+    /// When the cap is reached, a new text may only replace one of equal or lower priority.
+    pub fn priority(&self) -> u8 {
+        match self {
+            FloatingTextStyle::Damage => 0,
+            FloatingTextStyle::Heal | FloatingTextStyle::Shield => 1,
+            FloatingTextStyle::Dodge => 2,
+            FloatingTextStyle::Crit => 3,
+            FloatingTextStyle::Announcement
+            | FloatingTextStyle::Callout
+            | FloatingTextStyle::Notice => 4,
+        }
+    }
+
+    pub fn font_size(&self) -> f32 {
+        match self {
+            FloatingTextStyle::Damage | FloatingTextStyle::Heal | FloatingTextStyle::Shield => 18.0,
+            FloatingTextStyle::Dodge => 20.0,
+            FloatingTextStyle::Crit => 26.0,
+            FloatingTextStyle::Announcement => 48.0,
+            FloatingTextStyle::Callout => 34.0,
+            FloatingTextStyle::Notice => 26.0,
+        }
+    }
+
+    pub fn velocity(&self) -> Vec2 {
+        match self {
+            FloatingTextStyle::Damage | FloatingTextStyle::Crit => Vec2::new(0.0, 60.0),
+            FloatingTextStyle::Heal | FloatingTextStyle::Shield => Vec2::new(0.0, 50.0),
+            FloatingTextStyle::Dodge => Vec2::new(0.0, 70.0),
+            FloatingTextStyle::Announcement => Vec2::new(0.0, 30.0),
+            FloatingTextStyle::Callout => Vec2::new(0.0, 40.0),
+            FloatingTextStyle::Notice => Vec2::new(0.0, 25.0),
+        }
+    }
+
+    pub fn lifetime(&self) -> f32 {
+        match self {
+            FloatingTextStyle::Damage
+            | FloatingTextStyle::Crit
+            | FloatingTextStyle::Heal
+            | FloatingTextStyle::Shield => DAMAGE_NUMBER_LIFETIME,
+            FloatingTextStyle::Dodge => 0.6,
+            FloatingTextStyle::Announcement | FloatingTextStyle::Notice => 2.0,
+            FloatingTextStyle::Callout => 1.5,
+        }
+    }
+
+    /// Numbers hidden by the "Damage Numbers" setting.
+    pub fn is_number(&self) -> bool {
+        matches!(
+            self,
+            FloatingTextStyle::Damage
+                | FloatingTextStyle::Crit
+                | FloatingTextStyle::Heal
+                | FloatingTextStyle::Shield
+        )
+    }
+
+    /// Styles whose numbers merge into one running total; damage and crits share a total.
+    pub fn merge_group(&self) -> Option<u8> {
+        match self {
+            FloatingTextStyle::Damage | FloatingTextStyle::Crit => Some(0),
+            FloatingTextStyle::Heal => Some(1),
+            FloatingTextStyle::Shield => Some(2),
+            FloatingTextStyle::Dodge
+            | FloatingTextStyle::Announcement
+            | FloatingTextStyle::Callout
+            | FloatingTextStyle::Notice => None,
+        }
+    }
+
+    pub fn format_amount(&self, amount: f32) -> String {
+        match self {
+            FloatingTextStyle::Heal => format!("+{:.0}", amount),
+            FloatingTextStyle::Shield => format!("({:.0})", amount),
+            _ => format!("{:.0}", amount),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FloatingTextContent {
+    Amount(f32),
+    Label(String),
+}
+
+/// Pooled world-space text; inactive entries are hidden and reused by later requests.
 #[derive(Component)]
-pub struct DamageNumber {
+pub struct FloatingText {
+    pub style: FloatingTextStyle,
+    pub target: Option<Entity>,
+    pub amount: f32,
     pub velocity: Vec2,
     pub lifetime: Timer,
+    pub active: bool,
 }
 
 #[derive(Component)]
@@ -279,9 +995,227 @@ pub struct SelectionUi;
 #[derive(Component)]
 pub struct PassiveUi;
 
+#[derive(Component)]
+pub struct LevelUpUi;
+
+#[derive(Component)]
+pub struct UpgradeCard(pub usize);
+
+#[derive(Component)]
+pub struct UpgradeCardText(pub usize);
+
+#[derive(Component)]
+pub struct BanishButton(pub usize);
+
+#[derive(Component)]
+pub struct RerollButton;
+
+#[derive(Component)]
+pub struct LevelUpStatusText;
+
+#[derive(Component)]
+pub struct PauseMenuUi;
+
+#[derive(Component)]
+pub struct PauseMainPanel;
+
+#[derive(Component)]
+pub struct SettingsPanel;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseAction {
+    Resume,
+    Settings,
+    Restart,
+    QuitToClassSelect,
+    Back,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    Volume,
+    DamageNumbers,
+    WindowMode,
+    ScreenShake,
+    ZoomPunch,
+    HitStop,
+    SlowMotion,
+    Palette,
+    HighContrast,
+}
+
+impl SettingKind {
+    pub fn is_accessibility(&self) -> bool {
+        !matches!(
+            self,
+            SettingKind::Volume | SettingKind::DamageNumbers | SettingKind::WindowMode
+        )
+    }
+
+    pub fn is_toggle(&self) -> bool {
+        matches!(
+            self,
+            SettingKind::DamageNumbers
+                | SettingKind::WindowMode
+                | SettingKind::Palette
+                | SettingKind::HighContrast
+        )
+    }
+}
+
+#[derive(Component)]
+pub struct SettingButton {
+    pub kind: SettingKind,
+    pub step: f32,
+}
+
+#[derive(Component)]
+pub struct SettingValueText(pub SettingKind);
+
+/// High-contrast outline sprite that follows `target` and sits just behind it.
+#[derive(Component)]
+pub struct ContrastOutline {
+    pub target: Entity,
+}
+
+#[derive(Component)]
+pub struct HasContrastOutline;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundKind {
+    Hit,
+    Crit,
+    Kill,
+    PlayerHurt,
+    LevelUp,
+    SkillCast,
+    BossDarkening,
+    BossSpawn,
+    Pickup,
+    Shrine,
+}
+
+impl SoundKind {
+    pub const ALL: [SoundKind; 10] = [
+        SoundKind::Hit,
+        SoundKind::Crit,
+        SoundKind::Kill,
+        SoundKind::PlayerHurt,
+        SoundKind::LevelUp,
+        SoundKind::SkillCast,
+        SoundKind::BossDarkening,
+        SoundKind::BossSpawn,
+        SoundKind::Pickup,
+        SoundKind::Shrine,
+    ];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            SoundKind::Hit => "audio/sfx/hit.ogg",
+            SoundKind::Crit => "audio/sfx/crit.ogg",
+            SoundKind::Kill => "audio/sfx/kill.ogg",
+            SoundKind::PlayerHurt => "audio/sfx/player_hurt.ogg",
+            SoundKind::LevelUp => "audio/sfx/level_up.ogg",
+            SoundKind::SkillCast => "audio/sfx/skill_cast.ogg",
+            SoundKind::BossDarkening => "audio/sfx/boss_darkening.ogg",
+            SoundKind::BossSpawn => "audio/sfx/boss_spawn.ogg",
+            SoundKind::Pickup => "audio/sfx/pickup.ogg",
+            SoundKind::Shrine => "audio/sfx/shrine.ogg",
+        }
+    }
+
+    /// Most copies of this sound allowed to play at once, so hordes don't clip.
+    pub fn max_voices(&self) -> usize {
+        match self {
+            SoundKind::Hit => 6,
+            SoundKind::Crit | SoundKind::Kill | SoundKind::Pickup => 4,
+            SoundKind::PlayerHurt | SoundKind::SkillCast => 2,
+            SoundKind::LevelUp
+            | SoundKind::BossDarkening
+            | SoundKind::BossSpawn
+            | SoundKind::Shrine => 1,
+        }
+    }
+
+    pub fn volume(&self) -> f32 {
+        match self {
+            SoundKind::Hit | SoundKind::Pickup => 0.4,
+            SoundKind::Crit | SoundKind::Kill => 0.6,
+            SoundKind::PlayerHurt | SoundKind::SkillCast | SoundKind::Shrine => 0.7,
+            SoundKind::LevelUp | SoundKind::BossDarkening | SoundKind::BossSpawn => 0.9,
+        }
+    }
+
+    /// Random playback speed offset, applied as `1.0 ± variation`.
+    pub fn pitch_variation(&self) -> f32 {
+        match self {
+            SoundKind::Hit | SoundKind::Pickup => 0.15,
+            SoundKind::Crit | SoundKind::Kill | SoundKind::PlayerHurt => 0.1,
+            SoundKind::SkillCast => 0.05,
+            SoundKind::LevelUp
+            | SoundKind::BossDarkening
+            | SoundKind::BossSpawn
+            | SoundKind::Shrine => 0.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct SoundVoice(pub SoundKind);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicLayerKind {
+    Base,
+    Horde,
+    Boss,
+}
+
+impl MusicLayerKind {
+    pub const ALL: [MusicLayerKind; 3] = [
+        MusicLayerKind::Base,
+        MusicLayerKind::Horde,
+        MusicLayerKind::Boss,
+    ];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            MusicLayerKind::Base => "audio/music/base.ogg",
+            MusicLayerKind::Horde => "audio/music/horde.ogg",
+            MusicLayerKind::Boss => "audio/music/boss.ogg",
+        }
+    }
+}
+
+/// One looping stem of the soundtrack; layers share a tempo and fade in over each other.
+#[derive(Component)]
+pub struct MusicLayer {
+    pub kind: MusicLayerKind,
+    pub volume: f32,
+}
+
 #[derive(Component)]
 pub struct ClassButton(pub PlayerClass);
 
+#[derive(Component, Default)]
+pub struct BuildCodeInput {
+    pub text: String,
+    pub focused: bool,
+}
+
+/// A clipboard read in flight for the build code input.
+#[derive(Component)]
+pub struct ClipboardPaste(pub Task<Option<String>>);
+
+/// A clipboard write in flight for an exported build code.
+#[derive(Component)]
+pub struct ClipboardCopy(pub Task<bool>);
+
+#[derive(Component)]
+pub struct BuildCodeInputText;
+
+#[derive(Component)]
+pub struct BuildCodeStatus;
+
 #[derive(Component)]
 pub struct PetButton(pub PetType);
 
@@ -311,6 +1245,21 @@ pub struct AnimationConfig {
 #[derive(Component)]
 pub struct Taunt;
 
+/// Enemies taunted by a blocking Tank deal reduced damage to the player.
+#[derive(Component)]
+pub struct Taunted(pub Timer);
+
+#[derive(Component)]
+pub struct BonusPet;
+
+/// On a player while one of their Buffer pets is up; stats are recomputed with its floors.
+#[derive(Component)]
+pub struct PetBuffed;
+
+/// A pet knocked out at 0 life; it stays hidden and idle until the timer revives it.
+#[derive(Component)]
+pub struct PetDowned(pub Timer);
+
 #[derive(Component)]
 pub struct CameraShake {
     pub trauma: f32,
@@ -433,6 +1382,8 @@ pub enum ShrineType {
     Speed,
     Defense,
     CritChance,
+    Evasion,
+    Respec,
 }
 
 #[derive(Component)]
@@ -451,15 +1402,17 @@ pub struct ChallengeZone {
 #[derive(Component)]
 pub struct MiniBoss;
 
-#[derive(Component)]
-pub struct HordeWaveMarker;
-
 #[derive(Component)]
 pub struct BossEntrance {
     pub timer: Timer,
     pub phase: BossEntrancePhase,
 }
 
+#[derive(Component)]
+pub struct BossPhaseTransition {
+    pub timer: Timer,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BossEntrancePhase {
     Darkening,
@@ -467,6 +1420,117 @@ pub enum BossEntrancePhase {
     Complete,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BossPattern {
+    Slam { radius: f32, damage_mult: f32 },
+    Charge { speed: f32, duration: f32 },
+    ProjectileRing { count: u32, speed: f32 },
+    SummonAdds { count: u32 },
+    ArenaHazards { count: u32, radius: f32 },
+}
+
+#[derive(Debug, Clone)]
+pub struct BossPhase {
+    pub hp_threshold: f32,
+    pub patterns: Vec<BossPattern>,
+    pub pattern_interval: f32,
+    pub speed_mult: f32,
+    pub damage_mult: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct BossDefinition {
+    pub name: String,
+    pub min_tier: u32,
+    pub health: f32,
+    pub damage: f32,
+    pub speed: f32,
+    pub size: f32,
+    pub color: Color,
+    pub enrage_after: f32,
+    pub phases: Vec<BossPhase>,
+}
+
+#[derive(Component)]
+pub struct BossBrain {
+    pub definition: BossDefinition,
+    pub phase: usize,
+    pub pattern_index: usize,
+    pub pattern_timer: Timer,
+    pub enrage_timer: Timer,
+    pub enraged: bool,
+    pub base_damage: f32,
+    pub base_speed: f32,
+}
+
+impl BossBrain {
+    pub fn new(definition: BossDefinition, base_damage: f32, base_speed: f32) -> Self {
+        let interval = definition.phases[0].pattern_interval;
+        let enrage_after = definition.enrage_after;
+        Self {
+            definition,
+            phase: 0,
+            pattern_index: 0,
+            pattern_timer: Timer::from_seconds(interval, TimerMode::Once),
+            enrage_timer: Timer::from_seconds(enrage_after, TimerMode::Once),
+            enraged: false,
+            base_damage,
+            base_speed,
+        }
+    }
+
+    pub fn current_phase(&self) -> &BossPhase {
+        &self.definition.phases[self.phase]
+    }
+
+    pub fn pattern_interval(&self) -> f32 {
+        let interval = self.current_phase().pattern_interval;
+        if self.enraged {
+            interval * crate::constants::BOSS_ENRAGE_INTERVAL_MULT
+        } else {
+            interval
+        }
+    }
+
+    pub fn apply_multipliers(&self, enemy: &mut Enemy) {
+        let phase = self.current_phase();
+        let (damage_mult, speed_mult) = if self.enraged {
+            (
+                crate::constants::BOSS_ENRAGE_DAMAGE_MULT,
+                crate::constants::BOSS_ENRAGE_SPEED_MULT,
+            )
+        } else {
+            (1.0, 1.0)
+        };
+        enemy.damage = self.base_damage * phase.damage_mult * damage_mult;
+        enemy.speed = self.base_speed * phase.speed_mult * speed_mult;
+    }
+}
+
+#[derive(Component)]
+pub struct BossCharge {
+    pub windup: Timer,
+    pub direction: Vec2,
+    pub speed: f32,
+    pub duration: f32,
+    pub started: bool,
+    pub hit_player: bool,
+}
+
+#[derive(Component)]
+pub struct GroundTelegraph {
+    pub owner: Entity,
+    pub damage: f32,
+    pub radius: f32,
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+pub struct EnemyProjectile {
+    pub damage: f32,
+    pub owner: Entity,
+}
+
 #[derive(Component)]
 pub struct BossDeathEffect {
     pub timer: Timer,
@@ -488,5 +1552,77 @@ pub struct EliteAura {
     pub owner: Entity,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EliteAffix {
+    Vampiric,
+    FrozenAura,
+    MoltenTrail,
+    Teleporter,
+    Shielding,
+    ExtraFast,
+}
+
+impl EliteAffix {
+    pub const ALL: [EliteAffix; 6] = [
+        EliteAffix::Vampiric,
+        EliteAffix::FrozenAura,
+        EliteAffix::MoltenTrail,
+        EliteAffix::Teleporter,
+        EliteAffix::Shielding,
+        EliteAffix::ExtraFast,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EliteAffix::Vampiric => "Vampiric",
+            EliteAffix::FrozenAura => "Frozen",
+            EliteAffix::MoltenTrail => "Molten",
+            EliteAffix::Teleporter => "Teleporter",
+            EliteAffix::Shielding => "Shielding",
+            EliteAffix::ExtraFast => "Extra Fast",
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Vampiric {
+    pub leech_pct: f32,
+}
+
+#[derive(Component)]
+pub struct FrozenAura {
+    pub radius: f32,
+    pub slow: f32,
+}
+
+#[derive(Component)]
+pub struct AuraSlow(pub f32);
+
+#[derive(Component)]
+pub struct MoltenTrail {
+    pub drop_timer: Timer,
+    pub damage: f32,
+}
+
+#[derive(Component)]
+pub struct MoltenPool {
+    pub owner: Entity,
+    pub damage: f32,
+    pub radius: f32,
+    pub lifetime: Timer,
+    pub tick: Timer,
+}
+
+#[derive(Component)]
+pub struct Teleporter {
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+pub struct ShieldingAura {
+    pub radius: f32,
+    pub timer: Timer,
+}
+
 #[derive(Component)]
 pub struct ChallengeZoneVisual;
@@ -9,10 +9,38 @@ pub const PLAYER_Z: f32 = 10.0;
 
 // === COMBAT ===
 pub const PROJECTILE_HIT_RADIUS: f32 = 65.0;
-pub const MELEE_HIT_RADIUS: f32 = 110.0;
+pub const MELEE_HIT_PADDING: f32 = 25.0;
+pub const MELEE_DAMAGE_MULT: f32 = 1.8;
+pub const MELEE_COMBO_STEPS: usize = 3;
+pub const MELEE_COMBO_WINDOW: f32 = 0.9;
+pub const MELEE_COMBO_MULTIPLIERS: [f32; MELEE_COMBO_STEPS] = [1.0, 1.0, 1.0];
+pub const TANK_COMBO_MULTIPLIERS: [f32; MELEE_COMBO_STEPS] = [1.0, 1.3, 2.0];
 pub const PROJECTILE_SPEED: f32 = 550.0;
 pub const PROJECTILE_LIFETIME: f32 = 2.0;
 pub const KNOCKBACK_FORCE: f32 = 35.0;
+pub const CHAIN_SEARCH_RADIUS: f32 = 200.0;
+pub const HOMING_TURN_RATE: f32 = 5.0;
+pub const HOMING_RANGE: f32 = 300.0;
+pub const SPLIT_COUNT: u32 = 2;
+pub const FORK_ANGLE: f32 = 0.4;
+pub const RETURN_DISTANCE: f32 = 380.0;
+pub const RETURN_CATCH_RADIUS: f32 = 30.0;
+pub const ORBIT_RADIUS: f32 = 90.0;
+pub const ORBIT_ANGULAR_SPEED: f32 = 4.0;
+pub const ORBIT_MAX_PROJECTILES: usize = 3;
+pub const ORBIT_LIFETIME: f32 = 4.0;
+pub const ORBIT_REHIT_INTERVAL: f32 = 0.5;
+pub const MELEE_CRIT_BONUS: f32 = 0.05;
+pub const VOLLEY_CRIT_BONUS: f32 = 0.15;
+pub const MAX_EVASION: f32 = 0.75;
+pub const MAX_BLOCK_CHANCE: f32 = 0.75;
+pub const TANK_BLOCK_CHANCE: f32 = 0.25;
+pub const TANK_BLOCK_AMOUNT: f32 = 0.5;
+pub const ARCHER_EVASION: f32 = 0.1;
+pub const COMBAT_LOG_CAPACITY: usize = 2000;
+pub const DPS_METER_WINDOW: f32 = 5.0;
+pub const DEATH_RECAP_WINDOW: f32 = 6.0;
+pub const DEATH_RECAP_TOP_SOURCES: usize = 3;
 
 // === GAME FEEL ===
 pub const HITSTOP_NORMAL_FRAMES: u64 = 4;
@@ -34,6 +62,9 @@ pub const ZOOM_RETURN_DURATION: f32 = 0.15;
 pub const XP_PICKUP_RADIUS: f32 = 22.0;
 pub const XP_ATTRACT_RADIUS: f32 = 120.0;
 pub const XP_ORB_LIFETIME: f32 = 12.0;
+pub const LEVEL_UP_OFFER_COUNT: usize = 3;
+pub const LEVEL_UP_REROLLS: u32 = 3;
+pub const LEVEL_UP_BANISHES: u32 = 2;
 
 // === ENEMY ===
 pub const ENEMY_ATTACK_RANGE: f32 = 85.0;
@@ -42,6 +73,27 @@ pub const MAX_ENEMIES_BASE: u32 = 8;
 pub const MAX_ENEMIES_CAP: u32 = 30;
 pub const BOSS_SPAWN_INTERVAL: f32 = 67.5;
 pub const MINI_BOSS_SPAWN_INTERVAL: f32 = 30.0;
+pub const ENEMY_WINDUP_DURATION: f32 = 0.6;
+pub const ENEMY_RECOVERY_DURATION: f32 = 0.4;
+pub const ENEMY_ATTACK_HIT_FRAME: usize = 3;
+pub const ENEMY_ATTACK_FRAME_TIME: f32 = 0.1;
+pub const ENEMY_HITBOX_OFFSET: f32 = 50.0;
+pub const ENEMY_HITBOX_RADIUS: f32 = 60.0;
+pub const ENEMY_HITBOX_LIFETIME: f32 = 0.1;
+
+// === HAZARDS ===
+pub const HAZARD_BASE_COUNT: u32 = 6;
+pub const HAZARD_COUNT_PER_TIER: u32 = 2;
+pub const HAZARD_TIER_DAMAGE_SCALE: f32 = 0.3;
+pub const FIRE_PIT_RADIUS: f32 = 60.0;
+pub const FIRE_PIT_DAMAGE: f32 = 8.0;
+pub const FIRE_PIT_TICK: f32 = 0.5;
+pub const ICE_PATCH_RADIUS: f32 = 90.0;
+pub const ICE_PATCH_SLOW: f32 = 0.4;
+pub const ICE_PATCH_TICK: f32 = 1.0;
+pub const LIGHTNING_PYLON_RADIUS: f32 = 130.0;
+pub const LIGHTNING_PYLON_DAMAGE: f32 = 30.0;
+pub const LIGHTNING_PYLON_INTERVAL: f32 = 2.5;
 
 // === PROGRESSION EVENTS ===
 pub const EVENT_MIN_INTERVAL: f32 = 30.0;
@@ -49,28 +101,82 @@ pub const EVENT_MAX_INTERVAL: f32 = 45.0;
 pub const ELITE_HP_MULTIPLIER: f32 = 3.0;
 pub const ELITE_XP_MULTIPLIER: f32 = 3.0;
 pub const ELITE_AURA_RADIUS: f32 = 100.0;
+pub const ELITE_MAX_AFFIXES: usize = 3;
+pub const ELITE_VAMPIRIC_LEECH: f32 = 0.3;
+pub const ELITE_FROZEN_AURA_RADIUS: f32 = 220.0;
+pub const ELITE_FROZEN_AURA_SLOW: f32 = 0.35;
+pub const ELITE_MOLTEN_DROP_INTERVAL: f32 = 0.4;
+pub const ELITE_MOLTEN_POOL_RADIUS: f32 = 45.0;
+pub const ELITE_MOLTEN_POOL_LIFETIME: f32 = 3.0;
+pub const ELITE_MOLTEN_TICK: f32 = 0.5;
+pub const ELITE_MOLTEN_DAMAGE_MULT: f32 = 0.25;
+pub const ELITE_TELEPORT_INTERVAL: f32 = 5.0;
+pub const ELITE_TELEPORT_MIN_DISTANCE: f32 = 300.0;
+pub const ELITE_SHIELD_INTERVAL: f32 = 6.0;
+pub const ELITE_SHIELD_HP_PCT: f32 = 0.2;
+pub const ELITE_EXTRA_FAST_MULT: f32 = 1.6;
 pub const HORDE_WAVE_DURATION: f32 = 10.0;
 pub const HORDE_SPAWN_MULTIPLIER: f32 = 2.0;
 pub const TREASURE_GOBLIN_LIFETIME: f32 = 5.0;
 pub const TREASURE_GOBLIN_SPEED: f32 = 280.0;
 pub const TREASURE_GOBLIN_XP_MULT: u32 = 10;
 pub const SHRINE_BUFF_DURATION: f32 = 30.0;
+pub const SHRINE_EVASION_BONUS: f32 = 0.2;
 pub const CHALLENGE_ZONE_RADIUS: f32 = 150.0;
 pub const CHALLENGE_ZONE_DURATION: f32 = 15.0;
 pub const CHALLENGE_ZONE_XP_MULT: f32 = 2.0;
 pub const BOSS_ENTRANCE_DURATION: f32 = 1.5;
 pub const BOSS_DEATH_SLOWMO_DURATION: f32 = 1.0;
+pub const BOSS_PHASE_TRANSITION_DURATION: f32 = 1.0;
+pub const BOSS_SLAM_WINDUP: f32 = 0.9;
+pub const BOSS_CHARGE_WINDUP: f32 = 0.6;
+pub const BOSS_HAZARD_WINDUP: f32 = 1.4;
+pub const BOSS_ENRAGE_DAMAGE_MULT: f32 = 1.5;
+pub const BOSS_ENRAGE_SPEED_MULT: f32 = 1.3;
+pub const BOSS_ENRAGE_INTERVAL_MULT: f32 = 0.6;
+pub const ENEMY_PROJECTILE_HIT_RADIUS: f32 = 20.0;
+pub const ENEMY_PROJECTILE_LIFETIME: f32 = 3.0;
+
+// === PETS ===
+pub const PET_BASE_LIFE: f32 = 80.0;
+pub const PET_REVIVE_TIME: f32 = 8.0;
+pub const PET_SIZE: f32 = 16.0;
+pub const PET_BUFF_MIN_ARMOR: f32 = 25.0;
+pub const PET_BUFF_MIN_DAMAGE: f32 = 35.0;
 
 // === UI ===
 pub const HEALTH_BAR_WIDTH: f32 = 50.0;
 pub const HEALTH_BAR_HEIGHT: f32 = 8.0;
 pub const DAMAGE_NUMBER_LIFETIME: f32 = 0.7;
+pub const FLOATING_TEXT_CAP: usize = 80;
+pub const FLOATING_TEXT_MERGE_WINDOW: f32 = 0.3;
+pub const FLOATING_TEXT_GRAVITY: f32 = 120.0;
 
 // === SKILLS ===
 pub const DASH_COOLDOWN: f32 = 2.0;
 pub const DASH_SPEED: f32 = 900.0;
 pub const DASH_DURATION: f32 = 0.12;
 pub const NOVA_COOLDOWN_DEFAULT: f32 = 5.0;
+pub const GROUND_SKILL_RANGE: f32 = 450.0;
+pub const CHANNEL_MAX_DURATION: f32 = 3.0;
+pub const METEOR_COOLDOWN: f32 = 6.0;
+pub const METEOR_RADIUS: f32 = 110.0;
+pub const METEOR_DAMAGE_MULT: f32 = 1.2;
+pub const ARROW_RAIN_COOLDOWN: f32 = 7.0;
+pub const ARROW_RAIN_RADIUS: f32 = 140.0;
+pub const ARROW_RAIN_DAMAGE_MULT: f32 = 0.5;
+pub const WHIRLWIND_COOLDOWN: f32 = 8.0;
+pub const WHIRLWIND_RADIUS: f32 = 130.0;
+pub const WHIRLWIND_DAMAGE_MULT: f32 = 0.6;
+pub const SPIRIT_BEAM_COOLDOWN: f32 = 8.0;
+pub const SPIRIT_BEAM_LENGTH: f32 = 420.0;
+pub const SPIRIT_BEAM_WIDTH: f32 = 40.0;
+pub const SPIRIT_BEAM_DAMAGE_MULT: f32 = 0.5;
+pub const MAX_COOLDOWN_REDUCTION: f32 = 0.5;
+pub const EXTRA_PROJECTILE_SPREAD: f32 = 0.15;
+pub const TAUNT_DURATION: f32 = 3.0;
+pub const TAUNT_DAMAGE_REDUCTION: f32 = 0.25;
+pub const MIN_MAX_LIFE_MULT: f32 = 0.1;
 
 // === SCALING ===
 pub const LEVEL_HEALTH_SCALE: f32 = 0.25;
@@ -81,3 +187,43 @@ pub const LEVEL_XP_MULTIPLIER: f32 = 1.4;
 pub const MAX_ELEMENTAL_STACKS: u32 = 10;
 pub const FIRE_ARMOR_REDUCTION_PER_STACK: f32 = 0.05;
 pub const FIRE_MAX_ARMOR_REDUCTION: f32 = 0.50;
+pub const MAX_RESISTANCE: f32 = 0.75;
+pub const CHILL_SLOW_PER_STACK: f32 = 0.05;
+pub const CHILL_MAX_SLOW: f32 = 0.5;
+pub const SHOCK_DAMAGE_TAKEN_PER_STACK: f32 = 0.04;
+pub const PLAYER_BURN_DAMAGE_PER_STACK: f32 = 2.0;
+pub const ENEMY_BURN_DAMAGE_PER_STACK: f32 = 6.0;
+pub const BURN_TICK: f32 = 0.5;
+pub const FIRE_STACK_DURATION: f32 = 4.0;
+pub const ICE_STACK_DURATION: f32 = 3.0;
+pub const LIGHTNING_STACK_DURATION: f32 = 3.0;
+pub const FREEZE_DURATION: f32 = 1.5;
+pub const CHAIN_LIGHTNING_DAMAGE: f32 = 70.0;
+pub const CHAIN_LIGHTNING_RANGE: f32 = 250.0;
+pub const CHAIN_LIGHTNING_MAX_JUMPS: usize = 5;
+pub const CHAIN_LIGHTNING_FALLOFF: f32 = 0.75;
+pub const CHAIN_LIGHTNING_COOLDOWN: f32 = 3.0;
+pub const LIGHTNING_BOLT_DURATION: f32 = 0.2;
+pub const ENEMY_ELEMENTAL_CHANCE: f32 = 0.15;
+pub const ENEMY_ELEMENT_PROC_CHANCE: f32 = 0.35;
+pub const ELITE_ELEMENT_PROC_CHANCE: f32 = 0.6;
+
+// === SETTINGS ===
+pub const SETTINGS_PATH: &str = "settings.cfg";
+pub const VOLUME_STEP: f32 = 0.1;
+
+// === ACCESSIBILITY ===
+pub const ACCESSIBILITY_PATH: &str = "accessibility.cfg";
+pub const INTENSITY_STEP: f32 = 0.25;
+pub const MAX_SCREEN_SHAKE_SCALE: f32 = 2.0;
+pub const MAX_ZOOM_PUNCH_SCALE: f32 = 2.0;
+pub const CONTRAST_OUTLINE_WIDTH: f32 = 3.0;
+
+// === AUDIO ===
+pub const AUDIO_SPATIAL_SCALE: f32 = 1.0 / 400.0;
+pub const AUDIO_EAR_GAP: f32 = 200.0;
+pub const MUSIC_VOLUME: f32 = 0.5;
+pub const MUSIC_FADE_SPEED: f32 = 0.8;
+pub const MUSIC_LOOP_LENGTH: f32 = 4.0;
+pub const SYNTH_SAMPLE_RATE: u32 = 44_100;
+pub const SYNTH_ATTACK: f32 = 0.005;
@@ -1,4 +1,7 @@
-use crate::components::PassiveEffect;
+use crate::components::{
+    AttackKind, DamageSource, DamageType, DefenseOutcome, FloatingTextContent, FloatingTextStyle,
+    PassiveEffect, SoundKind,
+};
 use bevy::prelude::*;
 
 #[derive(Event)]
@@ -6,7 +9,24 @@ pub struct DamageEvent {
     pub target: Entity,
     pub attacker: Option<Entity>,
     pub amount: f32,
-    pub is_crit: bool,
+    pub damage_type: DamageType,
+    pub source: DamageSource,
+    pub kind: AttackKind,
+    pub crit_bonus: f32,
+}
+
+#[derive(Event)]
+pub struct DefenseEvent {
+    pub target: Entity,
+    pub outcome: DefenseOutcome,
+    pub position: Vec3,
+}
+
+#[derive(Event)]
+pub struct ProjectileHitEvent {
+    pub projectile: Entity,
+    pub target: Entity,
+    pub position: Vec2,
 }
 
 #[derive(Event)]
@@ -19,4 +39,66 @@ pub struct SpawnXpOrbEvent {
 pub struct ApplyStatusEvent {
     pub target: Entity,
     pub effect: PassiveEffect,
+    /// Entity credited for damage the status deals; `None` for environmental sources.
+    pub applier: Option<Entity>,
+}
+
+/// Tears the current run down; `keep_selection` restarts with the same class and pets.
+#[derive(Event)]
+pub struct RestartRunEvent {
+    pub keep_selection: bool,
+}
+
+/// Plays a sound effect; `position` pans it relative to the camera, `None` plays it centered.
+#[derive(Event)]
+pub struct SoundEvent {
+    pub kind: SoundKind,
+    pub position: Option<Vec2>,
+}
+
+/// Requests a floating text; numbers with a `target` merge with a recent one on the same target.
+#[derive(Event, Clone)]
+pub struct FloatingTextEvent {
+    pub content: FloatingTextContent,
+    pub style: FloatingTextStyle,
+    pub color: Color,
+    pub position: Vec3,
+    pub target: Option<Entity>,
+}
+
+impl FloatingTextEvent {
+    pub fn amount(
+        style: FloatingTextStyle,
+        amount: f32,
+        color: Color,
+        position: Vec3,
+        target: Entity,
+    ) -> Self {
+        Self {
+            content: FloatingTextContent::Amount(amount),
+            style,
+            color,
+            position,
+            target: Some(target),
+        }
+    }
+
+    pub fn label(
+        style: FloatingTextStyle,
+        label: impl Into<String>,
+        color: Color,
+        position: Vec3,
+    ) -> Self {
+        Self {
+            content: FloatingTextContent::Label(label.into()),
+            style,
+            color,
+            position,
+            target: None,
+        }
+    }
+
+    pub fn announcement(label: impl Into<String>, color: Color, position: Vec3) -> Self {
+        Self::label(FloatingTextStyle::Announcement, label, color, position)
+    }
 }
@@ -0,0 +1,103 @@
+use crate::components::PlayerClass;
+
+/// A shareable passive build, encoded as `<class>-<node ids in base 36>-<checksum>`,
+/// e.g. `MG-0.1.a-3F2C`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildCode {
+    pub class: PlayerClass,
+    pub nodes: Vec<u32>,
+}
+
+impl BuildCode {
+    pub fn encode(&self) -> String {
+        let body = encode_body(self.class, &self.nodes);
+        format!("{}-{:04X}", body, checksum(&body))
+    }
+
+    pub fn decode(code: &str) -> Option<Self> {
+        let mut parts = code.trim().split('-');
+        let class = match parts.next()?.to_ascii_uppercase().as_str() {
+            "TK" => PlayerClass::Tank,
+            "AR" => PlayerClass::Archer,
+            "MG" => PlayerClass::Mage,
+            "TM" => PlayerClass::Tamer,
+            _ => return None,
+        };
+        let ids = parts.next()?;
+        let sum = u16::from_str_radix(parts.next()?, 16).ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let nodes = if ids.is_empty() {
+            Vec::new()
+        } else {
+            ids.split('.')
+                .map(|id| u32::from_str_radix(id, 36).ok())
+                .collect::<Option<Vec<_>>>()?
+        };
+        (checksum(&encode_body(class, &nodes)) == sum).then_some(Self { class, nodes })
+    }
+}
+
+fn encode_body(class: PlayerClass, nodes: &[u32]) -> String {
+    let tag = match class {
+        PlayerClass::Tank => "TK",
+        PlayerClass::Archer => "AR",
+        PlayerClass::Mage => "MG",
+        PlayerClass::Tamer => "TM",
+    };
+    let ids = nodes
+        .iter()
+        .map(|&id| to_base36(id))
+        .collect::<Vec<_>>()
+        .join(".");
+    format!("{tag}-{ids}")
+}
+
+fn to_base36(mut value: u32) -> String {
+    let mut digits = Vec::new();
+    loop {
+        digits.push(std::char::from_digit(value % 36, 36).unwrap_or('0'));
+        value /= 36;
+        if value == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().collect()
+}
+
+/// FNV-1a folded down to 16 bits.
+fn checksum(body: &str) -> u16 {
+    let hash = body.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    });
+    ((hash >> 16) ^ (hash & 0xffff)) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_round_trip() {
+        let build = BuildCode {
+            class: PlayerClass::Mage,
+            nodes: vec![0, 1, 35, 36, 700],
+        };
+        let code = build.encode();
+        assert_eq!(BuildCode::decode(&code), Some(build));
+    }
+
+    #[test]
+    fn rejects_bad_checksum() {
+        let code = BuildCode {
+            class: PlayerClass::Archer,
+            nodes: vec![3, 12],
+        }
+        .encode();
+        let (body, sum) = code.rsplit_once('-').unwrap();
+        let wrong = u16::from_str_radix(sum, 16).unwrap().wrapping_add(1);
+        assert_eq!(BuildCode::decode(&format!("{body}-{wrong:04X}")), None);
+    }
+}
@@ -0,0 +1,78 @@
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on, poll_once};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// The system clipboard is reached through the platform's command-line tools; each
+// entry is tried in order until one succeeds.
+#[cfg(target_os = "macos")]
+const COPY_COMMANDS: &[(&str, &[&str])] = &[("pbcopy", &[])];
+#[cfg(target_os = "macos")]
+const PASTE_COMMANDS: &[(&str, &[&str])] = &[("pbpaste", &[])];
+
+#[cfg(target_os = "windows")]
+const COPY_COMMANDS: &[(&str, &[&str])] = &[("clip", &[])];
+#[cfg(target_os = "windows")]
+const PASTE_COMMANDS: &[(&str, &[&str])] =
+    &[("powershell", &["-NoProfile", "-Command", "Get-Clipboard"])];
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const COPY_COMMANDS: &[(&str, &[&str])] = &[
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const PASTE_COMMANDS: &[(&str, &[&str])] = &[
+    ("wl-paste", &["--no-newline"]),
+    ("xclip", &["-selection", "clipboard", "-o"]),
+    ("xsel", &["--clipboard", "--output"]),
+];
+
+/// Copies `text` to the system clipboard off the main thread; the task resolves to false
+/// if no clipboard tool worked.
+pub fn copy_to_clipboard(text: String) -> Task<bool> {
+    AsyncComputeTaskPool::get().spawn(async move { run_copy(&text) })
+}
+
+/// Reads the system clipboard as trimmed text off the main thread.
+pub fn read_clipboard() -> Task<Option<String>> {
+    AsyncComputeTaskPool::get().spawn(async { run_paste() })
+}
+
+/// Returns the task's result once it has finished, without blocking the frame.
+pub fn poll_clipboard<T>(task: &mut Task<T>) -> Option<T> {
+    block_on(poll_once(task))
+}
+
+fn run_copy(text: &str) -> bool {
+    COPY_COMMANDS.iter().any(|(program, args)| {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            return false;
+        };
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        child.wait().is_ok_and(|status| status.success()) && written
+    })
+}
+
+fn run_paste() -> Option<String> {
+    PASTE_COMMANDS.iter().find_map(|(program, args)| {
+        let output = Command::new(program)
+            .args(*args)
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    })
+}
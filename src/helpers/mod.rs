@@ -1,3 +1,11 @@
+mod build_code;
+mod clipboard;
 mod collision;
+mod synth;
+mod text_input;
 
+pub use build_code::*;
+pub use clipboard::*;
 pub use collision::*;
+pub use synth::*;
+pub use text_input::*;
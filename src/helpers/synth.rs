@@ -0,0 +1,156 @@
+use crate::constants::{SYNTH_ATTACK, SYNTH_SAMPLE_RATE};
+use bevy::audio::{Decodable, Source};
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wave {
+    Sine,
+    Square,
+    Triangle,
+    Noise,
+}
+
+/// One note of a `Synth`: a waveform gliding from `frequency` to `end_frequency`
+/// with a short attack and a linear fade over its duration.
+#[derive(Debug, Clone, Copy)]
+pub struct Tone {
+    pub wave: Wave,
+    pub start: f32,
+    pub duration: f32,
+    pub frequency: f32,
+    pub end_frequency: f32,
+    pub volume: f32,
+}
+
+impl Tone {
+    pub fn new(wave: Wave, start: f32, duration: f32, frequency: f32, volume: f32) -> Self {
+        Self {
+            wave,
+            start,
+            duration,
+            frequency,
+            end_frequency: frequency,
+            volume,
+        }
+    }
+
+    pub fn glide_to(self, end_frequency: f32) -> Self {
+        Self {
+            end_frequency,
+            ..self
+        }
+    }
+
+    fn sample(&self, t: f32, noise: f32) -> f32 {
+        let local = t - self.start;
+        if local < 0.0 || local >= self.duration {
+            return 0.0;
+        }
+        // Integrated frequency so glides don't click.
+        let sweep = (self.end_frequency - self.frequency) / self.duration;
+        let phase = (self.frequency * local + 0.5 * sweep * local * local).fract();
+        let value = match self.wave {
+            Wave::Sine => (phase * TAU).sin(),
+            Wave::Square => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Wave::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+            Wave::Noise => noise,
+        };
+        let envelope = (local / SYNTH_ATTACK).min(1.0) * (1.0 - local / self.duration);
+        value * envelope * self.volume
+    }
+}
+
+/// A procedurally generated sound, used when no recorded audio file is available.
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct Synth {
+    pub length: f32,
+    pub tones: Vec<Tone>,
+}
+
+impl Synth {
+    pub fn new(tones: Vec<Tone>) -> Self {
+        let length = tones
+            .iter()
+            .map(|tone| tone.start + tone.duration)
+            .fold(0.0, f32::max);
+        Self { length, tones }
+    }
+
+    /// Pads or trims the sound to `length` seconds, e.g. so looping layers stay in step.
+    pub fn with_length(self, length: f32) -> Self {
+        Self { length, ..self }
+    }
+}
+
+pub struct SynthDecoder {
+    synth: Synth,
+    sample: u32,
+    total: u32,
+    noise_state: u32,
+}
+
+impl Iterator for SynthDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.sample >= self.total {
+            return None;
+        }
+        let t = self.sample as f32 / SYNTH_SAMPLE_RATE as f32;
+        self.sample += 1;
+
+        // xorshift keeps noise deterministic, so every play of a sound is identical.
+        self.noise_state ^= self.noise_state << 13;
+        self.noise_state ^= self.noise_state >> 17;
+        self.noise_state ^= self.noise_state << 5;
+        let noise = self.noise_state as f32 / u32::MAX as f32 * 2.0 - 1.0;
+
+        let value: f32 = self
+            .synth
+            .tones
+            .iter()
+            .map(|tone| tone.sample(t, noise))
+            .sum();
+        Some(value.clamp(-1.0, 1.0))
+    }
+}
+
+impl Source for SynthDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        Some((self.total - self.sample) as usize)
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SYNTH_SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f32(self.synth.length))
+    }
+}
+
+impl Decodable for Synth {
+    type DecoderItem = f32;
+    type Decoder = SynthDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        SynthDecoder {
+            synth: self.clone(),
+            sample: 0,
+            total: (self.length * SYNTH_SAMPLE_RATE as f32) as u32,
+            noise_state: 0x9e37_79b9,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+
+/// Applies typed characters and backspace to `buffer`. Returns true if the text changed.
+pub fn read_text_input(
+    events: &mut EventReader<KeyboardInput>,
+    buffer: &mut String,
+    max_len: usize,
+) -> bool {
+    let mut changed = false;
+    for event in events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Backspace => changed |= buffer.pop().is_some(),
+            Key::Space if buffer.chars().count() < max_len => {
+                buffer.push(' ');
+                changed = true;
+            }
+            Key::Character(text) => {
+                for c in text.chars().filter(|c| !c.is_control()) {
+                    if buffer.chars().count() < max_len {
+                        buffer.push(c);
+                        changed = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    changed
+}
@@ -37,6 +37,10 @@ fn main() {
             SelectionPlugin,
             GameFeelPlugin,
             ProgressionPlugin,
+            BossPlugin,
+            LevelUpPlugin,
+            PausePlugin,
+            SoundPlugin,
         ))
         .add_systems(Startup, setup_camera_and_sprites)
         .add_systems(Update, systems::animation::animate_sprite)
@@ -56,6 +60,8 @@ fn setup_camera_and_sprites(
 
     let layout = TextureAtlasLayout::from_grid(UVec2::splat(100), 6, 1, None, None);
     let layout_handle = texture_atlases.add(layout);
+    let wide_layout = TextureAtlasLayout::from_grid(UVec2::splat(100), 9, 1, None, None);
+    let wide_layout_handle = texture_atlases.add(wide_layout);
 
     commands.insert_resource(CharacterSprites {
         orc_idle: asset_server.load("sprites/orc/idle.png"),
@@ -64,6 +70,9 @@ fn setup_camera_and_sprites(
         soldier_idle: asset_server.load("sprites/soldier/idle.png"),
         soldier_walk: asset_server.load("sprites/soldier/walk.png"),
         soldier_attack: asset_server.load("sprites/soldier/attack1.png"),
+        soldier_attack2: asset_server.load("sprites/soldier/attack2.png"),
+        soldier_attack3: asset_server.load("sprites/soldier/attack3.png"),
         layout: layout_handle,
+        wide_layout: wide_layout_handle,
     });
 }
@@ -0,0 +1,25 @@
+use bevy::audio::AddAudioSource;
+use bevy::prelude::*;
+
+use crate::events::*;
+use crate::helpers::Synth;
+use crate::systems::audio::*;
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_source::<Synth>()
+            .add_event::<SoundEvent>()
+            .add_systems(Startup, setup_audio)
+            .add_systems(
+                Update,
+                (
+                    attach_spatial_listener,
+                    start_music_layers,
+                    play_sound_effects,
+                    update_music_layers,
+                ),
+            );
+    }
+}
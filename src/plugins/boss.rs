@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::boss::*;
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BossRoster>()
+            .add_systems(Startup, setup_boss_roster)
+            .add_systems(
+                Update,
+                (
+                    update_boss_phases,
+                    update_boss_phase_transitions,
+                    update_boss_enrage,
+                    run_boss_patterns,
+                    update_boss_charge,
+                    update_ground_telegraphs,
+                    update_enemy_projectiles,
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+fn setup_boss_roster(mut roster: ResMut<BossRoster>) {
+    roster.bosses = vec![
+        BossDefinition {
+            name: "Orc Warlord".to_string(),
+            min_tier: 1,
+            health: 8000.0,
+            damage: 80.0,
+            speed: 140.0,
+            size: 400.0,
+            color: Color::srgb(0.5, 0.1, 0.1),
+            enrage_after: 120.0,
+            phases: vec![
+                BossPhase {
+                    hp_threshold: 1.0,
+                    patterns: vec![
+                        BossPattern::Slam {
+                            radius: 140.0,
+                            damage_mult: 1.0,
+                        },
+                        BossPattern::Charge {
+                            speed: 700.0,
+                            duration: 0.5,
+                        },
+                    ],
+                    pattern_interval: 4.0,
+                    speed_mult: 1.0,
+                    damage_mult: 1.0,
+                },
+                BossPhase {
+                    hp_threshold: 0.6,
+                    patterns: vec![
+                        BossPattern::ProjectileRing {
+                            count: 12,
+                            speed: 300.0,
+                        },
+                        BossPattern::Slam {
+                            radius: 160.0,
+                            damage_mult: 1.1,
+                        },
+                        BossPattern::SummonAdds { count: 3 },
+                        BossPattern::Charge {
+                            speed: 750.0,
+                            duration: 0.5,
+                        },
+                    ],
+                    pattern_interval: 3.0,
+                    speed_mult: 1.15,
+                    damage_mult: 1.2,
+                },
+                BossPhase {
+                    hp_threshold: 0.3,
+                    patterns: vec![
+                        BossPattern::ArenaHazards {
+                            count: 6,
+                            radius: 110.0,
+                        },
+                        BossPattern::ProjectileRing {
+                            count: 16,
+                            speed: 340.0,
+                        },
+                        BossPattern::Charge {
+                            speed: 800.0,
+                            duration: 0.6,
+                        },
+                        BossPattern::Slam {
+                            radius: 180.0,
+                            damage_mult: 1.3,
+                        },
+                    ],
+                    pattern_interval: 2.2,
+                    speed_mult: 1.3,
+                    damage_mult: 1.4,
+                },
+            ],
+        },
+        BossDefinition {
+            name: "Blood Chieftain".to_string(),
+            min_tier: 3,
+            health: 11000.0,
+            damage: 95.0,
+            speed: 150.0,
+            size: 440.0,
+            color: Color::srgb(0.6, 0.05, 0.2),
+            enrage_after: 100.0,
+            phases: vec![
+                BossPhase {
+                    hp_threshold: 1.0,
+                    patterns: vec![
+                        BossPattern::ProjectileRing {
+                            count: 10,
+                            speed: 320.0,
+                        },
+                        BossPattern::Charge {
+                            speed: 800.0,
+                            duration: 0.5,
+                        },
+                        BossPattern::SummonAdds { count: 2 },
+                    ],
+                    pattern_interval: 3.5,
+                    speed_mult: 1.0,
+                    damage_mult: 1.0,
+                },
+                BossPhase {
+                    hp_threshold: 0.5,
+                    patterns: vec![
+                        BossPattern::ArenaHazards {
+                            count: 8,
+                            radius: 120.0,
+                        },
+                        BossPattern::Slam {
+                            radius: 200.0,
+                            damage_mult: 1.2,
+                        },
+                        BossPattern::ProjectileRing {
+                            count: 20,
+                            speed: 360.0,
+                        },
+                        BossPattern::SummonAdds { count: 4 },
+                    ],
+                    pattern_interval: 2.5,
+                    speed_mult: 1.2,
+                    damage_mult: 1.3,
+                },
+            ],
+        },
+    ];
+}
@@ -2,22 +2,40 @@ use bevy::prelude::*;
 
 use crate::components::*;
 use crate::events::*;
+use crate::resources::*;
 use crate::systems::combat::*;
+use crate::systems::projectiles::*;
 
 pub struct CombatPlugin;
 
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<DamageEvent>()
+        app.insert_resource(CombatLog::new())
+            .init_resource::<DeathRecap>()
+            .add_event::<DamageEvent>()
+            .add_event::<DefenseEvent>()
+            .add_event::<ProjectileHitEvent>()
             .add_event::<SpawnXpOrbEvent>()
             .add_event::<ApplyStatusEvent>()
             .add_systems(
                 Update,
                 (
-                    update_projectiles,
+                    (
+                        steer_homing_projectiles,
+                        update_orbiting_projectiles,
+                        update_projectiles,
+                        update_returning_projectiles,
+                        chain_projectiles,
+                        fork_projectiles,
+                        split_projectiles,
+                        despawn_spent_projectiles,
+                    )
+                        .chain(),
                     update_melee_attacks,
                     update_aoe_effects,
                     process_damage,
+                    taunt_on_block,
+                    update_taunted,
                 )
                     .run_if(in_state(GameState::Playing)),
             );
@@ -14,11 +14,15 @@ impl Plugin for EnemyPlugin {
                 track_enemy_count,
                 enemy_ai,
                 enemy_attack,
+                update_attack_telegraphs,
+                update_enemy_hitboxes,
                 spawn_enemies,
                 check_enemy_death,
                 handle_status_applications,
                 update_elemental_statuses,
                 handle_mastery_effects,
+                chain_lightning_discharge,
+                update_lightning_bolts,
             )
                 .run_if(in_state(GameState::Playing)),
         );
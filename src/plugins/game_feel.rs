@@ -3,6 +3,7 @@ use rand::Rng;
 
 use crate::components::*;
 use crate::constants::*;
+use crate::events::*;
 use crate::resources::*;
 
 pub struct GameFeelPlugin;
@@ -30,6 +31,8 @@ impl Plugin for GameFeelPlugin {
                     update_kill_feedback,
                     update_knockback,
                     update_camera_zoom_punch,
+                    react_to_defense_events,
+                    sync_contrast_outlines,
                 )
                     .run_if(in_state(GameState::Playing)),
             );
@@ -85,6 +88,7 @@ fn camera_follow(
 fn update_screen_shake(
     time: Res<Time>,
     mut rng: ResMut<GameRng>,
+    accessibility: Res<Accessibility>,
     mut camera: Query<(&mut Transform, &mut CameraShake, &CameraTarget), With<Camera2d>>,
 ) {
     let Ok((mut transform, mut shake, camera_target)) = camera.get_single_mut() else {
@@ -95,7 +99,7 @@ fn update_screen_shake(
     let base_y = camera_target.position.y;
 
     if shake.trauma > 0.0 {
-        let shake_amount = shake.trauma * shake.trauma;
+        let shake_amount = shake.trauma * shake.trauma * accessibility.screen_shake;
 
         let (dir_bias_x, dir_bias_y) = if let Some(dir) = shake.direction {
             let normalized = dir.normalize_or_zero();
@@ -128,9 +132,20 @@ fn update_screen_shake(
     }
 }
 
-fn update_hit_stop(time: Res<Time>, mut hit_stop: ResMut<HitStop>) {
+/// Lower intensities run the timer faster, so stops end sooner; 0 skips them entirely.
+fn update_hit_stop(
+    time: Res<Time>,
+    accessibility: Res<Accessibility>,
+    mut hit_stop: ResMut<HitStop>,
+) {
     if hit_stop.active {
-        hit_stop.timer.tick(time.delta());
+        if accessibility.hit_stop <= 0.0 {
+            hit_stop.active = false;
+            return;
+        }
+        hit_stop
+            .timer
+            .tick(time.delta().div_f32(accessibility.hit_stop));
         if hit_stop.timer.finished() {
             hit_stop.active = false;
         }
@@ -156,6 +171,38 @@ fn flash_on_damage(
     }
 }
 
+fn react_to_defense_events(
+    mut defense_events: EventReader<DefenseEvent>,
+    player: Query<Entity, With<Player>>,
+    mut camera: Query<&mut CameraShake, With<Camera2d>>,
+    mut text_events: EventWriter<FloatingTextEvent>,
+) {
+    let player_entity = player.get_single().ok();
+
+    for event in defense_events.read() {
+        let (label, color) = match event.outcome {
+            DefenseOutcome::Evaded => ("DODGE", Color::srgb(0.6, 1.0, 0.9)),
+            DefenseOutcome::Blocked(_) => ("BLOCK", Color::srgb(0.6, 0.7, 1.0)),
+        };
+
+        text_events.send(FloatingTextEvent::label(
+            FloatingTextStyle::Dodge,
+            label,
+            color,
+            event.position + Vec3::new(0.0, 50.0, 100.0),
+        ));
+
+        if Some(event.target) != player_entity {
+            continue;
+        }
+        if let (DefenseOutcome::Blocked(_), Ok(mut shake)) =
+            (event.outcome, camera.get_single_mut())
+        {
+            add_trauma(&mut shake, 0.1);
+        }
+    }
+}
+
 fn update_level_up_rings(
     mut commands: Commands,
     time: Res<Time>,
@@ -303,9 +350,20 @@ fn update_death_particles(
     }
 }
 
-fn update_slow_motion(time: Res<Time>, mut slow_mo: ResMut<SlowMotion>) {
+fn update_slow_motion(
+    time: Res<Time>,
+    accessibility: Res<Accessibility>,
+    mut slow_mo: ResMut<SlowMotion>,
+) {
     if slow_mo.active {
-        slow_mo.timer.tick(time.delta());
+        if accessibility.slow_motion <= 0.0 {
+            let duration = slow_mo.timer.duration();
+            slow_mo.timer.set_elapsed(duration);
+        } else {
+            slow_mo
+                .timer
+                .tick(time.delta().div_f32(accessibility.slow_motion));
+        }
         if slow_mo.timer.finished() {
             slow_mo.active = false;
             slow_mo.time_scale = 1.0;
@@ -377,6 +435,7 @@ fn update_knockback(
 fn update_camera_zoom_punch(
     mut commands: Commands,
     time: Res<Time>,
+    accessibility: Res<Accessibility>,
     mut query: Query<(Entity, &mut OrthographicProjection, &mut CameraZoomPunch), With<Camera2d>>,
 ) {
     for (entity, mut projection, mut zoom_punch) in query.iter_mut() {
@@ -392,7 +451,7 @@ fn update_camera_zoom_punch(
             }
         } else {
             let t = zoom_punch.timer.fraction();
-            let zoom_scale = 1.0 - ZOOM_PUNCH_AMOUNT;
+            let zoom_scale = 1.0 - ZOOM_PUNCH_AMOUNT * accessibility.zoom_punch;
             if zoom_punch.returning {
                 let ease = t;
                 projection.scale =
@@ -407,6 +466,89 @@ fn update_camera_zoom_punch(
     }
 }
 
+const ENEMY_OUTLINE_COLOR: Color = Color::WHITE;
+const PROJECTILE_OUTLINE_COLOR: Color = Color::srgb(1.0, 1.0, 0.0);
+const ENEMY_PROJECTILE_OUTLINE_COLOR: Color = Color::srgb(1.0, 0.0, 1.0);
+
+/// The outline is a solid rectangle the size of the sprite plus a border, drawn just
+/// behind it, not a true silhouette, so transparent sprite corners show the fill.
+fn sync_contrast_outlines(
+    mut commands: Commands,
+    accessibility: Res<Accessibility>,
+    targets: Query<
+        (
+            Entity,
+            &Transform,
+            &Sprite,
+            Has<Enemy>,
+            Has<EnemyProjectile>,
+            Has<HasContrastOutline>,
+        ),
+        (
+            Or<(With<Enemy>, With<Projectile>, With<EnemyProjectile>)>,
+            Without<ContrastOutline>,
+        ),
+    >,
+    mut outlines: Query<(Entity, &ContrastOutline, &mut Transform, &mut Sprite)>,
+) {
+    if !accessibility.high_contrast {
+        if accessibility.is_changed() {
+            for (entity, outline, _, _) in outlines.iter() {
+                commands.entity(entity).despawn();
+                if let Some(mut target) = commands.get_entity(outline.target) {
+                    target.remove::<HasContrastOutline>();
+                }
+            }
+        }
+        return;
+    }
+
+    for (entity, outline, mut transform, mut sprite) in outlines.iter_mut() {
+        let Ok((_, target_transform, target_sprite, ..)) = targets.get(outline.target) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        transform.translation = target_transform.translation - Vec3::Z * 0.05;
+        transform.rotation = target_transform.rotation;
+        transform.scale = target_transform.scale;
+        sprite.custom_size = target_sprite
+            .custom_size
+            .map(|size| size + Vec2::splat(CONTRAST_OUTLINE_WIDTH * 2.0));
+    }
+
+    for (entity, transform, sprite, is_enemy, is_enemy_projectile, outlined) in targets.iter() {
+        let Some(size) = sprite.custom_size else {
+            continue;
+        };
+        if outlined {
+            continue;
+        }
+        let color = if is_enemy {
+            ENEMY_OUTLINE_COLOR
+        } else if is_enemy_projectile {
+            ENEMY_PROJECTILE_OUTLINE_COLOR
+        } else {
+            PROJECTILE_OUTLINE_COLOR
+        };
+        commands.spawn((
+            ContrastOutline { target: entity },
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(size + Vec2::splat(CONTRAST_OUTLINE_WIDTH * 2.0)),
+                    ..default()
+                },
+                transform: Transform {
+                    translation: transform.translation - Vec3::Z * 0.05,
+                    ..*transform
+                },
+                ..default()
+            },
+        ));
+        commands.entity(entity).try_insert(HasContrastOutline);
+    }
+}
+
 pub fn add_directional_trauma(shake: &mut CameraShake, amount: f32, direction: Vec2) {
     shake.trauma = (shake.trauma + amount).min(1.0);
     shake.direction = Some(direction);
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::level_up::*;
+
+pub struct LevelUpPlugin;
+
+impl Plugin for LevelUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelUpOffers>()
+            .add_systems(OnEnter(GameState::LevelUp), setup_level_up_ui)
+            .add_systems(
+                Update,
+                (
+                    handle_level_up_reroll,
+                    handle_level_up_choice,
+                    update_level_up_ui,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::LevelUp)),
+            )
+            .add_systems(OnExit(GameState::LevelUp), despawn_level_up_ui);
+    }
+}
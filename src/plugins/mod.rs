@@ -1,17 +1,25 @@
+pub mod audio;
+pub mod boss;
 pub mod combat;
 pub mod enemy;
 pub mod game_feel;
+pub mod level_up;
 pub mod passive_tree;
+pub mod pause;
 pub mod player;
 pub mod progression;
 pub mod selection;
 pub mod ui;
 pub mod world;
 
+pub use audio::SoundPlugin;
+pub use boss::BossPlugin;
 pub use combat::CombatPlugin;
 pub use enemy::EnemyPlugin;
 pub use game_feel::GameFeelPlugin;
+pub use level_up::LevelUpPlugin;
 pub use passive_tree::PassiveTreePlugin;
+pub use pause::PausePlugin;
 pub use player::PlayerPlugin;
 pub use progression::ProgressionPlugin;
 pub use selection::SelectionPlugin;
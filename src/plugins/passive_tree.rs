@@ -15,9 +15,15 @@ impl Plugin for PassiveTreePlugin {
                 Update,
                 (
                     toggle_passive_ui,
+                    handle_passive_search,
+                    handle_legend_filters,
+                    handle_export_build,
+                    finish_build_code_copy,
                     handle_passive_tree_controls,
                     track_hovered_node,
                     handle_node_click,
+                    handle_respec_button,
+                    handle_planning_button,
                     update_passive_ui,
                 )
                     .chain()
@@ -27,7 +33,8 @@ impl Plugin for PassiveTreePlugin {
             .add_systems(
                 Update,
                 toggle_passive_ui.run_if(in_state(GameState::Playing)),
-            );
+            )
+            .add_systems(Update, apply_planned_allocations);
     }
 }
 
@@ -43,6 +50,12 @@ fn setup_passive_tree_data(mut commands: Commands) {
         crit_multiplier: 0.0,
         life_regen: 0.0,
         armor: 0.0,
+        fire_resistance: 0.0,
+        cold_resistance: 0.0,
+        lightning_resistance: 0.0,
+        evasion: 0.0,
+        block_chance: 0.0,
+        block_amount: 0.0,
     };
 
     nodes.insert(
@@ -55,8 +68,11 @@ fn setup_passive_tree_data(mut commands: Commands) {
                 damage: 10.0,
                 ..zero_stats
             }),
-            requirements: vec![],
+            requirements: vec![300, 400, 500, 600],
             position: Vec2::ZERO,
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
         },
     );
 
@@ -72,6 +88,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             }),
             requirements: vec![0],
             position: Vec2::new(120.0, 0.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -86,6 +105,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             }),
             requirements: vec![200],
             position: Vec2::new(240.0, 60.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -100,6 +122,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             }),
             requirements: vec![200],
             position: Vec2::new(240.0, -60.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -111,6 +136,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::LifeLeech(0.015),
             requirements: vec![201],
             position: Vec2::new(360.0, 100.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -125,6 +153,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             }),
             requirements: vec![200],
             position: Vec2::new(120.0, 100.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -139,6 +170,40 @@ fn setup_passive_tree_data(mut commands: Commands) {
             }),
             requirements: vec![204],
             position: Vec2::new(120.0, 200.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        206,
+        PassiveNode {
+            id: 206,
+            name: "Fleet Footed".to_string(),
+            description: "+8% Evasion".to_string(),
+            effect: PassiveEffect::StatAdd(Stats {
+                evasion: 0.08,
+                ..zero_stats
+            }),
+            requirements: vec![202],
+            position: Vec2::new(180.0, -160.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        207,
+        PassiveNode {
+            id: 207,
+            name: "Fortune's Favor".to_string(),
+            description: "Crit chance is rolled twice".to_string(),
+            effect: PassiveEffect::LuckyCrit,
+            requirements: vec![206],
+            position: Vec2::new(200.0, -270.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -150,6 +215,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::Knockback,
             requirements: vec![200],
             position: Vec2::new(0.0, 120.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -161,6 +229,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::Explosion,
             requirements: vec![201],
             position: Vec2::new(360.0, 20.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -172,6 +243,79 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::Ricochet,
             requirements: vec![202],
             position: Vec2::new(360.0, -100.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        19,
+        PassiveNode {
+            id: 19,
+            name: "Seeker Rounds".to_string(),
+            description: "Projectiles home in on nearby enemies".to_string(),
+            effect: PassiveEffect::Homing,
+            requirements: vec![10],
+            position: Vec2::new(480.0, -40.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        20,
+        PassiveNode {
+            id: 20,
+            name: "Splinter".to_string(),
+            description: "Projectiles split into 2 on first hit".to_string(),
+            effect: PassiveEffect::SplitProjectiles,
+            requirements: vec![10],
+            position: Vec2::new(480.0, -130.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        21,
+        PassiveNode {
+            id: 21,
+            name: "Forked Tongue".to_string(),
+            description: "Projectiles fork on first hit".to_string(),
+            effect: PassiveEffect::ForkProjectiles,
+            requirements: vec![19],
+            position: Vec2::new(600.0, -10.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        22,
+        PassiveNode {
+            id: 22,
+            name: "Boomerang".to_string(),
+            description: "Projectiles return to you, piercing all".to_string(),
+            effect: PassiveEffect::ReturningProjectiles,
+            requirements: vec![20],
+            position: Vec2::new(600.0, -130.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        23,
+        PassiveNode {
+            id: 23,
+            name: "Orbiting Shards".to_string(),
+            description: "Attacks spawn shards that orbit you (max 3)".to_string(),
+            effect: PassiveEffect::OrbitingProjectiles,
+            requirements: vec![19],
+            position: Vec2::new(600.0, 70.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
         },
     );
 
@@ -187,6 +331,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             }),
             requirements: vec![0],
             position: Vec2::new(-120.0, 0.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -201,6 +348,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             }),
             requirements: vec![100],
             position: Vec2::new(-240.0, 60.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -212,6 +362,46 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::ShieldRegen(8.0),
             requirements: vec![100],
             position: Vec2::new(-240.0, -60.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        103,
+        PassiveNode {
+            id: 103,
+            name: "Elemental Ward".to_string(),
+            description: "+15% All Resistances".to_string(),
+            effect: PassiveEffect::StatAdd(Stats {
+                fire_resistance: 0.15,
+                cold_resistance: 0.15,
+                lightning_resistance: 0.15,
+                ..zero_stats
+            }),
+            requirements: vec![101],
+            position: Vec2::new(-300.0, 160.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        108,
+        PassiveNode {
+            id: 108,
+            name: "Shield Wall".to_string(),
+            description: "+10% Block Chance, +15% Block Amount".to_string(),
+            effect: PassiveEffect::StatAdd(Stats {
+                block_chance: 0.10,
+                block_amount: 0.15,
+                ..zero_stats
+            }),
+            requirements: vec![101],
+            position: Vec2::new(-160.0, 170.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -223,6 +413,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::ShieldLeech(0.03),
             requirements: vec![102],
             position: Vec2::new(-360.0, -100.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -234,6 +427,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::StatAdd(Stats { ..zero_stats }),
             requirements: vec![100],
             position: Vec2::new(-120.0, -100.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -248,6 +444,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             }),
             requirements: vec![105],
             position: Vec2::new(-200.0, -180.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -262,6 +461,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             }),
             requirements: vec![105],
             position: Vec2::new(-40.0, -180.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
 
@@ -274,6 +476,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::ChanceFire(0.20),
             requirements: vec![201],
             position: Vec2::new(340.0, 140.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -285,6 +490,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::MasteryFire,
             requirements: vec![11],
             position: Vec2::new(460.0, 180.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -296,6 +504,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::ChanceIce(0.25),
             requirements: vec![102],
             position: Vec2::new(-340.0, 20.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -307,6 +518,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::MasteryIce,
             requirements: vec![14],
             position: Vec2::new(-460.0, 60.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -318,6 +532,9 @@ fn setup_passive_tree_data(mut commands: Commands) {
             effect: PassiveEffect::ChanceLightning(0.15),
             requirements: vec![202],
             position: Vec2::new(340.0, -180.0),
+            class: None,
+            tier: NodeTier::Minor,
+            drawback: None,
         },
     );
     nodes.insert(
@@ -325,10 +542,290 @@ fn setup_passive_tree_data(mut commands: Commands) {
         PassiveNode {
             id: 18,
             name: "Chain Lightning".to_string(),
-            description: "Discharge at 10 stacks".to_string(),
+            description: "Discharge arcs between shocked enemies at 10 stacks".to_string(),
             effect: PassiveEffect::MasteryLightning,
             requirements: vec![17],
             position: Vec2::new(460.0, -220.0),
+            class: None,
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+
+    nodes.insert(
+        300,
+        PassiveNode {
+            id: 300,
+            name: "Juggernaut".to_string(),
+            description: "Tank start. +5% Block Chance".to_string(),
+            effect: PassiveEffect::BlockChance(0.05),
+            requirements: vec![],
+            position: Vec2::new(-80.0, 320.0),
+            class: Some(PlayerClass::Tank),
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        301,
+        PassiveNode {
+            id: 301,
+            name: "Stalwart Guard".to_string(),
+            description: "+10% Block Chance".to_string(),
+            effect: PassiveEffect::BlockChance(0.10),
+            requirements: vec![300],
+            position: Vec2::new(-200.0, 380.0),
+            class: Some(PlayerClass::Tank),
+            tier: NodeTier::Minor,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        302,
+        PassiveNode {
+            id: 302,
+            name: "Provoke".to_string(),
+            description: "Blocking taunts enemies nearby: they deal 25% less damage".to_string(),
+            effect: PassiveEffect::Taunt(250.0),
+            requirements: vec![300],
+            position: Vec2::new(40.0, 380.0),
+            class: Some(PlayerClass::Tank),
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        303,
+        PassiveNode {
+            id: 303,
+            name: "Iron Bulwark".to_string(),
+            description: "+8% Block Chance".to_string(),
+            effect: PassiveEffect::BlockChance(0.08),
+            requirements: vec![301],
+            position: Vec2::new(-200.0, 480.0),
+            class: Some(PlayerClass::Tank),
+            tier: NodeTier::Minor,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        400,
+        PassiveNode {
+            id: 400,
+            name: "Ranger".to_string(),
+            description: "Archer start. Projectiles pierce 1 more enemy".to_string(),
+            effect: PassiveEffect::ProjectilePierce(1),
+            requirements: vec![],
+            position: Vec2::new(300.0, -360.0),
+            class: Some(PlayerClass::Archer),
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        401,
+        PassiveNode {
+            id: 401,
+            name: "Twin Shot".to_string(),
+            description: "+1 Projectile".to_string(),
+            effect: PassiveEffect::ProjectileCount(1),
+            requirements: vec![400],
+            position: Vec2::new(420.0, -400.0),
+            class: Some(PlayerClass::Archer),
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        402,
+        PassiveNode {
+            id: 402,
+            name: "Piercing Arrows".to_string(),
+            description: "Projectiles pierce 2 more enemies".to_string(),
+            effect: PassiveEffect::ProjectilePierce(2),
+            requirements: vec![400],
+            position: Vec2::new(300.0, -480.0),
+            class: Some(PlayerClass::Archer),
+            tier: NodeTier::Minor,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        403,
+        PassiveNode {
+            id: 403,
+            name: "Barrage".to_string(),
+            description: "+1 Projectile".to_string(),
+            effect: PassiveEffect::ProjectileCount(1),
+            requirements: vec![401],
+            position: Vec2::new(540.0, -440.0),
+            class: Some(PlayerClass::Archer),
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        500,
+        PassiveNode {
+            id: 500,
+            name: "Arcanist".to_string(),
+            description: "Mage start. 10% reduced Skill Cooldowns".to_string(),
+            effect: PassiveEffect::SkillCooldown(0.10),
+            requirements: vec![],
+            position: Vec2::new(260.0, 300.0),
+            class: Some(PlayerClass::Mage),
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        501,
+        PassiveNode {
+            id: 501,
+            name: "Quickened Casting".to_string(),
+            description: "15% reduced Skill Cooldowns".to_string(),
+            effect: PassiveEffect::SkillCooldown(0.15),
+            requirements: vec![500],
+            position: Vec2::new(380.0, 340.0),
+            class: Some(PlayerClass::Mage),
+            tier: NodeTier::Minor,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        502,
+        PassiveNode {
+            id: 502,
+            name: "Expanded Reach".to_string(),
+            description: "25% increased Skill Area".to_string(),
+            effect: PassiveEffect::AoeSize(0.25),
+            requirements: vec![500],
+            position: Vec2::new(260.0, 420.0),
+            class: Some(PlayerClass::Mage),
+            tier: NodeTier::Minor,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        503,
+        PassiveNode {
+            id: 503,
+            name: "Cataclysm".to_string(),
+            description: "25% increased Skill Area".to_string(),
+            effect: PassiveEffect::AoeSize(0.25),
+            requirements: vec![502],
+            position: Vec2::new(380.0, 460.0),
+            class: Some(PlayerClass::Mage),
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        600,
+        PassiveNode {
+            id: 600,
+            name: "Beastmaster".to_string(),
+            description: "Tamer start. Pets deal 25% more damage".to_string(),
+            effect: PassiveEffect::PetDamage(0.25),
+            requirements: vec![],
+            position: Vec2::new(-300.0, -340.0),
+            class: Some(PlayerClass::Tamer),
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        601,
+        PassiveNode {
+            id: 601,
+            name: "Pack Leader".to_string(),
+            description: "+1 Pet".to_string(),
+            effect: PassiveEffect::PetCount(1),
+            requirements: vec![600],
+            position: Vec2::new(-420.0, -380.0),
+            class: Some(PlayerClass::Tamer),
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        602,
+        PassiveNode {
+            id: 602,
+            name: "Feral Strength".to_string(),
+            description: "Pets deal 50% more damage".to_string(),
+            effect: PassiveEffect::PetDamage(0.5),
+            requirements: vec![600],
+            position: Vec2::new(-300.0, -460.0),
+            class: Some(PlayerClass::Tamer),
+            tier: NodeTier::Minor,
+            drawback: None,
+        },
+    );
+    nodes.insert(
+        603,
+        PassiveNode {
+            id: 603,
+            name: "Nurturing Bond".to_string(),
+            description: "Pets have 100% more life".to_string(),
+            effect: PassiveEffect::PetLife(1.0),
+            requirements: vec![601],
+            position: Vec2::new(-540.0, -420.0),
+            class: Some(PlayerClass::Tamer),
+            tier: NodeTier::Notable,
+            drawback: None,
+        },
+    );
+
+    nodes.insert(
+        700,
+        PassiveNode {
+            id: 700,
+            name: "Glass Cannon".to_string(),
+            description: "100% more Damage".to_string(),
+            effect: PassiveEffect::Compound(&[
+                PassiveEffect::MoreDamage(1.0),
+                PassiveEffect::LessMaxLife(0.5),
+            ]),
+            requirements: vec![12],
+            position: Vec2::new(580.0, 230.0),
+            class: None,
+            tier: NodeTier::Keystone,
+            drawback: Some("Half maximum Life".to_string()),
+        },
+    );
+    nodes.insert(
+        701,
+        PassiveNode {
+            id: 701,
+            name: "Blood Magic".to_string(),
+            description: "30% reduced Skill Cooldowns".to_string(),
+            effect: PassiveEffect::Compound(&[
+                PassiveEffect::SkillCooldown(0.30),
+                PassiveEffect::SkillLifeCost(0.08),
+            ]),
+            requirements: vec![107],
+            position: Vec2::new(-40.0, -300.0),
+            class: None,
+            tier: NodeTier::Keystone,
+            drawback: Some("Skills cost 8% of maximum Life".to_string()),
+        },
+    );
+    nodes.insert(
+        702,
+        PassiveNode {
+            id: 702,
+            name: "Unwavering Stance".to_string(),
+            description: "+20% Block Chance".to_string(),
+            effect: PassiveEffect::Compound(&[
+                PassiveEffect::BlockChance(0.20),
+                PassiveEffect::CannotEvade,
+            ]),
+            requirements: vec![103],
+            position: Vec2::new(-420.0, 230.0),
+            class: None,
+            tier: NodeTier::Keystone,
+            drawback: Some("Cannot Evade".to_string()),
         },
     );
 
@@ -339,12 +836,21 @@ fn setup_passive_tree_data(mut commands: Commands) {
         (201, 203),
         (200, 204),
         (204, 205),
+        (202, 206),
+        (206, 207),
         (200, 8),
         (202, 10),
+        (10, 19),
+        (10, 20),
+        (19, 21),
+        (20, 22),
+        (19, 23),
         (201, 9),
         (0, 100),
         (100, 101),
         (100, 102),
+        (101, 103),
+        (101, 108),
         (102, 104),
         (100, 105),
         (105, 106),
@@ -355,6 +861,25 @@ fn setup_passive_tree_data(mut commands: Commands) {
         (14, 15),
         (202, 17),
         (17, 18),
+        (300, 0),
+        (300, 301),
+        (300, 302),
+        (301, 303),
+        (400, 0),
+        (400, 401),
+        (400, 402),
+        (401, 403),
+        (500, 0),
+        (500, 501),
+        (500, 502),
+        (502, 503),
+        (600, 0),
+        (600, 601),
+        (600, 602),
+        (601, 603),
+        (12, 700),
+        (107, 701),
+        (103, 702),
     ]);
 
     commands.insert_resource(PassiveTree { nodes, connections });
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::pause::*;
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Settings::load())
+            .insert_resource(Accessibility::load())
+            .init_resource::<PauseMenuState>()
+            .add_systems(Update, (toggle_pause, apply_window_mode))
+            .add_systems(
+                OnEnter(GameState::Paused),
+                (pause_virtual_time, setup_pause_menu),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_pause_buttons,
+                    handle_setting_buttons,
+                    update_pause_menu,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Paused)),
+            )
+            .add_systems(
+                OnExit(GameState::Paused),
+                (resume_virtual_time, despawn_pause_menu),
+            );
+    }
+}
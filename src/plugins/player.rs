@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 
 use crate::components::*;
+use crate::systems::pets::*;
 use crate::systems::player::*;
+use crate::systems::skills::*;
 
 pub struct PlayerPlugin;
 
@@ -13,12 +15,24 @@ impl Plugin for PlayerPlugin {
                 player_movement,
                 player_attack,
                 player_skills,
+                class_skill_input,
+                update_ground_target_indicators,
+                update_channeled_skills,
                 update_dash,
                 update_invulnerability,
                 regen_health,
+                update_player_ailments,
                 check_player_death,
+                update_pets,
+                pet_actions,
+                sync_bonus_pets,
+                sync_pet_life,
+                update_downed_pets,
+                sync_pet_buffs,
             )
                 .run_if(in_state(GameState::Playing)),
-        );
+        )
+        .add_systems(OnExit(GameState::Playing), clear_ground_target_indicators)
+        .add_systems(Update, recompute_player_stats);
     }
 }
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 use crate::components::*;
 use crate::resources::*;
+use crate::systems::affixes::*;
 use crate::systems::progression::*;
 
 pub struct ProgressionPlugin;
@@ -33,6 +34,17 @@ impl Plugin for ProgressionPlugin {
                     handle_boss_death,
                 )
                     .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    frozen_aura_slow,
+                    molten_trail_drop,
+                    update_molten_pools,
+                    elite_teleport,
+                    elite_shielding,
+                )
+                    .run_if(in_state(GameState::Playing)),
             );
     }
 }
@@ -1,14 +1,17 @@
 use bevy::prelude::*;
 
 use crate::components::*;
-use crate::systems::combat::update_damage_numbers;
+use crate::events::*;
+use crate::systems::floating_text::*;
 use crate::systems::ui::*;
 
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Playing), setup_hud)
+        app.add_event::<FloatingTextEvent>()
+            .add_systems(OnEnter(GameState::Playing), setup_hud)
+            .add_systems(Update, show_floating_texts)
             .add_systems(
                 Update,
                 (
@@ -19,8 +22,10 @@ impl Plugin for UIPlugin {
                     update_skill_cooldowns_ui,
                     toggle_stats_panel,
                     update_stats_panel,
+                    toggle_dps_meter,
+                    update_dps_meter,
                     update_buff_display,
-                    update_damage_numbers,
+                    update_floating_texts.after(show_floating_texts),
                     spawn_boss_health_bar,
                     update_boss_health_bar,
                     despawn_boss_health_bar,
@@ -120,6 +125,7 @@ pub struct SkillCooldownText {
 pub enum SkillType {
     Dash,
     Nova,
+    Special,
 }
 
 #[derive(Component)]
@@ -128,6 +134,12 @@ pub struct StatsPanelRoot;
 #[derive(Component)]
 pub struct StatsPanelContent;
 
+#[derive(Component)]
+pub struct DpsMeterRoot;
+
+#[derive(Component)]
+pub struct DpsMeterContent;
+
 #[derive(Component)]
 pub struct BuffContainer;
 
@@ -147,6 +159,9 @@ pub enum BuffType {
     SpeedBoost,
     DamageBoost,
     Invulnerable,
+    Ignited,
+    Chilled,
+    Shocked,
 }
 
 fn setup_hud(mut commands: Commands, existing_hud: Query<Entity, With<HudRoot>>) {
@@ -179,6 +194,9 @@ fn setup_hud(mut commands: Commands, existing_hud: Query<Entity, With<HudRoot>>)
             // === TOP-RIGHT: STATS PANEL (Tab Toggle) ===
             spawn_stats_panel(parent);
 
+            // === BOTTOM-RIGHT: DPS METER (M Toggle) ===
+            spawn_dps_meter(parent);
+
             // === BELOW HP: BUFF/DEBUFF ICONS ===
             spawn_buff_section(parent);
 
@@ -389,7 +407,7 @@ fn spawn_skills_section(parent: &mut ChildBuilder) {
                     bottom: Val::Px(80.0),
                     left: Val::Percent(50.0),
                     margin: UiRect {
-                        left: Val::Px(-130.0),
+                        left: Val::Px(-168.0),
                         ..default()
                     },
                     flex_direction: FlexDirection::Row,
@@ -418,6 +436,13 @@ fn spawn_skills_section(parent: &mut ChildBuilder) {
                 "NOVA",
                 Color::srgb(1.0, 0.5, 0.3),
             );
+            spawn_skill_slot(
+                container,
+                SkillType::Special,
+                "E",
+                "SKILL",
+                Color::srgb(0.5, 1.0, 0.6),
+            );
         });
 }
 
@@ -564,6 +589,51 @@ fn spawn_stats_panel(parent: &mut ChildBuilder) {
         });
 }
 
+fn spawn_dps_meter(parent: &mut ChildBuilder) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(20.0),
+                    right: Val::Px(20.0),
+                    min_width: Val::Px(180.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(4.0),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(UI_BG_MEDIUM),
+                border_color: BorderColor(UI_BORDER),
+                border_radius: BorderRadius::all(Val::Px(6.0)),
+                ..default()
+            },
+            DpsMeterRoot,
+        ))
+        .with_children(|panel| {
+            panel.spawn(TextBundle::from_section(
+                "DAMAGE METER",
+                TextStyle {
+                    font_size: 13.0,
+                    color: UI_BORDER_ACCENT,
+                    ..default()
+                },
+            ));
+            panel.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 12.0,
+                        color: UI_TEXT_SECONDARY,
+                        ..default()
+                    },
+                ),
+                DpsMeterContent,
+            ));
+        });
+}
+
 fn spawn_buff_section(parent: &mut ChildBuilder) {
     parent.spawn((
         NodeBundle {
@@ -671,7 +741,7 @@ fn spawn_xp_section(parent: &mut ChildBuilder) {
 fn spawn_controls_hint(parent: &mut ChildBuilder) {
     parent.spawn(
         TextBundle::from_section(
-            "WASD: Move | LMB: Shoot | RMB: Melee | Q: Dash | Space: Nova | Tab: Stats | P: Passives",
+            "WASD: Move | LMB: Shoot | RMB: Melee | Q: Dash | Space: Nova | E: Skill | Tab: Stats | M: Meter | P: Passives",
             TextStyle {
                 font_size: 12.0,
                 color: UI_TEXT_MUTED,
@@ -1,8 +1,21 @@
-use crate::components::{PassiveNode, PetType, PlayerClass};
+use crate::components::{
+    AttackKind, BossDefinition, DamageType, MusicLayerKind, PassiveEffect, PassiveNode, PetType,
+    PlayerClass, SettingKind, SoundKind, Stats, Upgrade,
+};
+use crate::constants::{
+    ACCESSIBILITY_PATH, COMBAT_LOG_CAPACITY, DEATH_RECAP_WINDOW, INTENSITY_STEP, LEVEL_UP_BANISHES,
+    LEVEL_UP_OFFER_COUNT, LEVEL_UP_REROLLS, MAX_COOLDOWN_REDUCTION, MAX_SCREEN_SHAKE_SCALE,
+    MAX_ZOOM_PUNCH_SCALE, MIN_MAX_LIFE_MULT, SETTINGS_PATH, VOLUME_STEP,
+};
+use crate::helpers::{BuildCode, Synth};
 use bevy::prelude::*;
-use rand::SeedableRng;
+use bevy::window::WindowMode;
 use rand::rngs::StdRng;
-use std::collections::HashMap;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{LineWriter, Write};
 
 #[derive(Resource, Default)]
 pub struct EnemyCount(pub usize);
@@ -55,10 +68,518 @@ pub struct PassiveTree {
     pub connections: Vec<(u32, u32)>,
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PassiveBonuses {
+    pub taunt_radius: f32,
+    pub extra_projectiles: u32,
+    pub extra_pierce: u32,
+    pub cooldown_reduction: f32,
+    pub aoe_size: f32,
+    pub pet_damage: f32,
+    pub pet_life: f32,
+    pub extra_pets: u32,
+    pub less_max_life: f32,
+    pub skill_life_cost: f32,
+    pub ricochet: u32,
+    pub homing: bool,
+    pub split_on_hit: bool,
+    pub fork_on_hit: bool,
+    pub returning: bool,
+    pub orbiting: bool,
+}
+
+impl PassiveBonuses {
+    pub fn cooldown_mult(&self) -> f32 {
+        1.0 - self.cooldown_reduction.clamp(0.0, MAX_COOLDOWN_REDUCTION)
+    }
+
+    pub fn max_life_mult(&self) -> f32 {
+        (1.0 - self.less_max_life).max(MIN_MAX_LIFE_MULT)
+    }
+}
+
+impl PassiveTree {
+    pub fn class_root(&self, class: PlayerClass) -> Option<u32> {
+        self.nodes
+            .values()
+            .find(|node| node.class == Some(class) && node.requirements.is_empty())
+            .map(|node| node.id)
+    }
+
+    /// Every allocation has to connect back to a root, and a class can only
+    /// start from (and take nodes exclusive to) its own class.
+    pub fn can_allocate(&self, node_id: u32, unlocked: &[u32], class: PlayerClass) -> bool {
+        let Some(node) = self.nodes.get(&node_id) else {
+            return false;
+        };
+        if unlocked.contains(&node_id) || node.class.is_some_and(|c| c != class) {
+            return false;
+        }
+        if node.requirements.is_empty() {
+            return node.class == Some(class);
+        }
+        node.requirements.iter().any(|req| unlocked.contains(req))
+    }
+
+    /// A node can be refunded if every other allocated node still traces back to a root.
+    pub fn can_refund(&self, node_id: u32, unlocked: &[u32]) -> bool {
+        let remaining: Vec<u32> = unlocked
+            .iter()
+            .copied()
+            .filter(|&id| id != node_id)
+            .collect();
+        if remaining.is_empty() {
+            return true;
+        }
+
+        let mut reached: HashSet<u32> = remaining
+            .iter()
+            .copied()
+            .filter(|id| {
+                self.nodes
+                    .get(id)
+                    .is_some_and(|node| node.requirements.is_empty())
+            })
+            .collect();
+        loop {
+            let before = reached.len();
+            for &id in &remaining {
+                let connected = self
+                    .nodes
+                    .get(&id)
+                    .is_some_and(|node| node.requirements.iter().any(|req| reached.contains(req)));
+                if connected {
+                    reached.insert(id);
+                }
+            }
+            if reached.len() == before {
+                break;
+            }
+        }
+        reached.len() == remaining.len()
+    }
+
+    /// Effects of the allocated nodes, with keystone compounds flattened out.
+    pub fn effects<'a>(&'a self, unlocked: &'a [u32]) -> impl Iterator<Item = &'a PassiveEffect> {
+        unlocked
+            .iter()
+            .filter_map(|id| self.nodes.get(id))
+            .flat_map(|node| match &node.effect {
+                PassiveEffect::Compound(effects) => effects,
+                effect => std::slice::from_ref(effect),
+            })
+    }
+
+    pub fn bonuses(&self, unlocked: &[u32]) -> PassiveBonuses {
+        let mut bonuses = PassiveBonuses::default();
+        for effect in self.effects(unlocked) {
+            match *effect {
+                PassiveEffect::Taunt(radius) => {
+                    bonuses.taunt_radius = bonuses.taunt_radius.max(radius)
+                }
+                PassiveEffect::ProjectileCount(count) => bonuses.extra_projectiles += count,
+                PassiveEffect::ProjectilePierce(pierce) => bonuses.extra_pierce += pierce,
+                PassiveEffect::SkillCooldown(pct) => bonuses.cooldown_reduction += pct,
+                PassiveEffect::AoeSize(pct) => bonuses.aoe_size += pct,
+                PassiveEffect::PetDamage(pct) => bonuses.pet_damage += pct,
+                PassiveEffect::PetLife(pct) => bonuses.pet_life += pct,
+                PassiveEffect::PetCount(count) => bonuses.extra_pets += count,
+                PassiveEffect::LessMaxLife(pct) => bonuses.less_max_life += pct,
+                PassiveEffect::SkillLifeCost(pct) => bonuses.skill_life_cost += pct,
+                PassiveEffect::Ricochet => bonuses.ricochet += 1,
+                PassiveEffect::Homing => bonuses.homing = true,
+                PassiveEffect::SplitProjectiles => bonuses.split_on_hit = true,
+                PassiveEffect::ForkProjectiles => bonuses.fork_on_hit = true,
+                PassiveEffect::ReturningProjectiles => bonuses.returning = true,
+                PassiveEffect::OrbitingProjectiles => bonuses.orbiting = true,
+                _ => {}
+            }
+        }
+        bonuses
+    }
+
+    pub fn allocated_stats(&self, base: &Stats, unlocked: &[u32]) -> Stats {
+        let mut stats = *base;
+        let mut more_damage = 1.0;
+        let mut cannot_evade = false;
+        for effect in self.effects(unlocked) {
+            match effect {
+                PassiveEffect::StatAdd(add) => stats.add_flat(add),
+                PassiveEffect::BlockChance(chance) => stats.block_chance += chance,
+                PassiveEffect::MoreDamage(pct) => more_damage *= 1.0 + pct,
+                PassiveEffect::CannotEvade => cannot_evade = true,
+                _ => {}
+            }
+        }
+        stats.damage *= more_damage;
+        if cannot_evade {
+            // Negative so evasion bonuses from shrines can't lift it back above zero.
+            stats.evasion = -1.0;
+        }
+        stats
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct PendingSelection {
     pub class: Option<PlayerClass>,
     pub pets: Vec<PetType>,
+    pub build: Option<BuildCode>,
+}
+
+#[derive(Resource)]
+pub struct LevelUpOffers {
+    /// Level-ups earned but not yet spent on an upgrade.
+    pub pending: u32,
+    pub offers: Vec<Upgrade>,
+    pub rerolls: u32,
+    pub banishes: u32,
+    pub banished: HashSet<Upgrade>,
+}
+
+impl Default for LevelUpOffers {
+    fn default() -> Self {
+        Self {
+            pending: 0,
+            offers: Vec::new(),
+            rerolls: LEVEL_UP_REROLLS,
+            banishes: LEVEL_UP_BANISHES,
+            banished: HashSet::new(),
+        }
+    }
+}
+
+impl LevelUpOffers {
+    fn draw(&self, class: PlayerClass, rng: &mut impl Rng) -> Option<Upgrade> {
+        let pool: Vec<Upgrade> = Upgrade::ALL
+            .into_iter()
+            .filter(|upgrade| !self.banished.contains(upgrade) && !self.offers.contains(upgrade))
+            .filter(|upgrade| upgrade.weight(class) > 0)
+            .collect();
+        pool.choose_weighted(rng, |upgrade| upgrade.weight(class))
+            .ok()
+            .copied()
+    }
+
+    pub fn roll(&mut self, class: PlayerClass, rng: &mut impl Rng) {
+        self.offers.clear();
+        while self.offers.len() < LEVEL_UP_OFFER_COUNT {
+            let Some(upgrade) = self.draw(class, rng) else {
+                break;
+            };
+            self.offers.push(upgrade);
+        }
+    }
+
+    pub fn banish(&mut self, index: usize, class: PlayerClass, rng: &mut impl Rng) {
+        let Some(&upgrade) = self.offers.get(index) else {
+            return;
+        };
+        self.banished.insert(upgrade);
+        self.banishes -= 1;
+        match self.draw(class, rng) {
+            Some(replacement) => self.offers[index] = replacement,
+            None => {
+                self.offers.remove(index);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowModeSetting {
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl WindowModeSetting {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WindowModeSetting::Windowed => "Windowed",
+            WindowModeSetting::Borderless => "Borderless",
+            WindowModeSetting::Fullscreen => "Fullscreen",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            WindowModeSetting::Windowed => WindowModeSetting::Borderless,
+            WindowModeSetting::Borderless => WindowModeSetting::Fullscreen,
+            WindowModeSetting::Fullscreen => WindowModeSetting::Windowed,
+        }
+    }
+
+    pub fn window_mode(&self) -> WindowMode {
+        match self {
+            WindowModeSetting::Windowed => WindowMode::Windowed,
+            WindowModeSetting::Borderless => WindowMode::BorderlessFullscreen,
+            WindowModeSetting::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+fn read_config(path: &str) -> Vec<(String, String)> {
+    std::fs::read_to_string(path)
+        .map(|text| {
+            text.lines()
+                .filter_map(|line| line.split_once('='))
+                .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn write_config(path: &str, text: String) {
+    if let Err(err) = std::fs::write(path, text) {
+        warn!("Could not save settings to {path}: {err}");
+    }
+}
+
+/// Player preferences, stored as `key=value` lines in `SETTINGS_PATH`.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub volume: f32,
+    pub damage_numbers: bool,
+    pub window_mode: WindowModeSetting,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            volume: 0.8,
+            damage_numbers: true,
+            window_mode: WindowModeSetting::Windowed,
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let mut settings = Self::default();
+        for (key, value) in read_config(SETTINGS_PATH) {
+            match key.as_str() {
+                "volume" => {
+                    settings.volume = value.parse().unwrap_or(settings.volume).clamp(0.0, 1.0)
+                }
+                "damage_numbers" => {
+                    settings.damage_numbers = value.parse().unwrap_or(settings.damage_numbers)
+                }
+                "window_mode" => {
+                    settings.window_mode = match value.as_str() {
+                        "Borderless" => WindowModeSetting::Borderless,
+                        "Fullscreen" => WindowModeSetting::Fullscreen,
+                        _ => WindowModeSetting::Windowed,
+                    }
+                }
+                _ => {}
+            }
+        }
+        settings
+    }
+
+    pub fn save(&self) {
+        let text = format!(
+            "volume={:.2}\ndamage_numbers={}\nwindow_mode={}\n",
+            self.volume,
+            self.damage_numbers,
+            self.window_mode.name(),
+        );
+        write_config(SETTINGS_PATH, text);
+    }
+
+    /// Steps a setting by `step` (-1 or +1); toggles ignore the direction.
+    pub fn adjust(&mut self, kind: SettingKind, step: f32) {
+        match kind {
+            SettingKind::Volume => {
+                self.volume = (self.volume + step * VOLUME_STEP).clamp(0.0, 1.0);
+            }
+            SettingKind::DamageNumbers => self.damage_numbers = !self.damage_numbers,
+            SettingKind::WindowMode => self.window_mode = self.window_mode.next(),
+            _ => {}
+        }
+    }
+
+    pub fn value_label(&self, kind: SettingKind) -> String {
+        match kind {
+            SettingKind::Volume => format!("{:.0}%", self.volume * 100.0),
+            SettingKind::DamageNumbers => on_off(self.damage_numbers),
+            SettingKind::WindowMode => self.window_mode.name().to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+fn on_off(on: bool) -> String {
+    if on { "On" } else { "Off" }.to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorPalette {
+    #[default]
+    Standard,
+    /// Deuteranopia / protanopia: avoids relying on red vs green.
+    RedGreen,
+    /// Tritanopia: avoids relying on blue vs yellow.
+    BlueYellow,
+}
+
+impl ColorPalette {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorPalette::Standard => "Standard",
+            ColorPalette::RedGreen => "Red-Green",
+            ColorPalette::BlueYellow => "Blue-Yellow",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ColorPalette::Standard => ColorPalette::RedGreen,
+            ColorPalette::RedGreen => ColorPalette::BlueYellow,
+            ColorPalette::BlueYellow => ColorPalette::Standard,
+        }
+    }
+
+    pub fn frozen_tint(&self) -> Color {
+        match self {
+            ColorPalette::Standard => Color::srgb(0.3, 0.6, 1.0),
+            ColorPalette::RedGreen => Color::srgb(0.0, 0.45, 0.7),
+            ColorPalette::BlueYellow => Color::srgb(0.9, 0.95, 1.0),
+        }
+    }
+
+    /// Tint for enemies whose dominant ailment is of `damage_type`.
+    pub fn element_tint(&self, damage_type: DamageType) -> Color {
+        match (self, damage_type) {
+            (ColorPalette::Standard, DamageType::Fire) => Color::srgb(1.0, 0.5, 0.5),
+            (ColorPalette::Standard, DamageType::Cold) => Color::srgb(0.5, 0.8, 1.0),
+            (ColorPalette::Standard, DamageType::Lightning) => Color::srgb(1.0, 1.0, 0.5),
+            (ColorPalette::RedGreen, DamageType::Fire) => Color::srgb(0.9, 0.6, 0.0),
+            (ColorPalette::RedGreen, DamageType::Cold) => Color::srgb(0.35, 0.7, 0.9),
+            (ColorPalette::RedGreen, DamageType::Lightning) => Color::srgb(0.95, 0.9, 0.25),
+            (ColorPalette::BlueYellow, DamageType::Fire) => Color::srgb(1.0, 0.3, 0.3),
+            (ColorPalette::BlueYellow, DamageType::Cold) => Color::srgb(0.3, 0.9, 0.9),
+            (ColorPalette::BlueYellow, DamageType::Lightning) => Color::srgb(1.0, 0.5, 0.9),
+            _ => Color::WHITE,
+        }
+    }
+}
+
+/// Accessibility options, stored alongside `Settings` in `ACCESSIBILITY_PATH`.
+/// Intensities scale the matching game-feel effect; 0 turns it off.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Accessibility {
+    pub screen_shake: f32,
+    pub zoom_punch: f32,
+    pub hit_stop: f32,
+    pub slow_motion: f32,
+    pub palette: ColorPalette,
+    pub high_contrast: bool,
+}
+
+impl Default for Accessibility {
+    fn default() -> Self {
+        Self {
+            screen_shake: 1.0,
+            zoom_punch: 1.0,
+            hit_stop: 1.0,
+            slow_motion: 1.0,
+            palette: ColorPalette::Standard,
+            high_contrast: false,
+        }
+    }
+}
+
+impl Accessibility {
+    fn intensity_mut(&mut self, kind: SettingKind) -> Option<(&mut f32, f32)> {
+        match kind {
+            SettingKind::ScreenShake => Some((&mut self.screen_shake, MAX_SCREEN_SHAKE_SCALE)),
+            SettingKind::ZoomPunch => Some((&mut self.zoom_punch, MAX_ZOOM_PUNCH_SCALE)),
+            SettingKind::HitStop => Some((&mut self.hit_stop, 1.0)),
+            SettingKind::SlowMotion => Some((&mut self.slow_motion, 1.0)),
+            _ => None,
+        }
+    }
+
+    pub fn load() -> Self {
+        let mut accessibility = Self::default();
+        for (key, value) in read_config(ACCESSIBILITY_PATH) {
+            let kind = match key.as_str() {
+                "screen_shake" => SettingKind::ScreenShake,
+                "zoom_punch" => SettingKind::ZoomPunch,
+                "hit_stop" => SettingKind::HitStop,
+                "slow_motion" => SettingKind::SlowMotion,
+                "palette" => {
+                    accessibility.palette = match value.as_str() {
+                        "Red-Green" => ColorPalette::RedGreen,
+                        "Blue-Yellow" => ColorPalette::BlueYellow,
+                        _ => ColorPalette::Standard,
+                    };
+                    continue;
+                }
+                "high_contrast" => {
+                    accessibility.high_contrast =
+                        value.parse().unwrap_or(accessibility.high_contrast);
+                    continue;
+                }
+                _ => continue,
+            };
+            if let Some((intensity, max)) = accessibility.intensity_mut(kind) {
+                *intensity = value.parse().unwrap_or(*intensity).clamp(0.0, max);
+            }
+        }
+        accessibility
+    }
+
+    pub fn save(&self) {
+        let text = format!(
+            "screen_shake={:.2}\nzoom_punch={:.2}\nhit_stop={:.2}\nslow_motion={:.2}\npalette={}\nhigh_contrast={}\n",
+            self.screen_shake,
+            self.zoom_punch,
+            self.hit_stop,
+            self.slow_motion,
+            self.palette.name(),
+            self.high_contrast,
+        );
+        write_config(ACCESSIBILITY_PATH, text);
+    }
+
+    pub fn adjust(&mut self, kind: SettingKind, step: f32) {
+        match kind {
+            SettingKind::Palette => self.palette = self.palette.next(),
+            SettingKind::HighContrast => self.high_contrast = !self.high_contrast,
+            _ => {
+                if let Some((intensity, max)) = self.intensity_mut(kind) {
+                    *intensity = (*intensity + step * INTENSITY_STEP).clamp(0.0, max);
+                }
+            }
+        }
+    }
+
+    pub fn value_label(&self, kind: SettingKind) -> String {
+        match kind {
+            SettingKind::ScreenShake => format!("{:.0}%", self.screen_shake * 100.0),
+            SettingKind::ZoomPunch => format!("{:.0}%", self.zoom_punch * 100.0),
+            SettingKind::HitStop => format!("{:.0}%", self.hit_stop * 100.0),
+            SettingKind::SlowMotion => format!("{:.0}%", self.slow_motion * 100.0),
+            SettingKind::Palette => self.palette.name().to_string(),
+            SettingKind::HighContrast => on_off(self.high_contrast),
+            _ => String::new(),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SoundLibrary {
+    pub effects: HashMap<SoundKind, SoundSources>,
+    pub music: HashMap<MusicLayerKind, SoundSources>,
+}
+
+/// The recorded file for a sound, plus a generated stand-in played until (or unless) it loads.
+pub struct SoundSources {
+    pub file: Handle<AudioSource>,
+    pub placeholder: Handle<Synth>,
 }
 
 #[derive(Resource)]
@@ -69,7 +590,10 @@ pub struct CharacterSprites {
     pub soldier_idle: Handle<Image>,
     pub soldier_walk: Handle<Image>,
     pub soldier_attack: Handle<Image>,
+    pub soldier_attack2: Handle<Image>,
+    pub soldier_attack3: Handle<Image>,
     pub layout: Handle<TextureAtlasLayout>,
+    pub wide_layout: Handle<TextureAtlasLayout>,
 }
 
 #[derive(Resource, Default)]
@@ -79,8 +603,10 @@ pub struct CursorWorldPos(pub Vec2);
 pub struct GameStats {
     pub enemies_killed: u32,
     pub damage_dealt: f32,
+    pub damage_taken: f32,
     pub time_survived: f32,
     pub show_stats: bool,
+    pub show_dps_meter: bool,
 }
 
 impl Default for GameStats {
@@ -88,12 +614,170 @@ impl Default for GameStats {
         Self {
             enemies_killed: 0,
             damage_dealt: 0.0,
+            damage_taken: 0.0,
             time_survived: 0.0,
             show_stats: false,
+            show_dps_meter: true,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct CombatLogEntry {
+    pub time: f32,
+    pub attacker: Option<Entity>,
+    pub target: Entity,
+    pub kind: AttackKind,
+    pub damage_type: DamageType,
+    pub raw: f32,
+    pub mitigated: f32,
+    pub is_crit: bool,
+    pub killed: bool,
+    pub target_is_player: bool,
+}
+
+impl CombatLogEntry {
+    pub fn is_player_damage(&self) -> bool {
+        !self.target_is_player && AttackKind::PLAYER_SOURCES.contains(&self.kind)
+    }
+
+    fn to_json(self) -> String {
+        let attacker = self
+            .attacker
+            .map_or("null".to_string(), |e| e.to_bits().to_string());
+        format!(
+            "{{\"time\":{:.3},\"attacker\":{},\"target\":{},\"kind\":\"{}\",\"damage_type\":\"{:?}\",\"raw\":{:.2},\"mitigated\":{:.2},\"crit\":{},\"killed\":{},\"target_is_player\":{}}}",
+            self.time,
+            attacker,
+            self.target.to_bits(),
+            self.kind.name(),
+            self.damage_type,
+            self.raw,
+            self.mitigated,
+            self.is_crit,
+            self.killed,
+            self.target_is_player,
+        )
+    }
+}
+
+/// Every resolved hit, newest last. Set `COMBAT_LOG_PATH` to also append
+/// each entry as a JSON line to that file.
+#[derive(Resource, Default)]
+pub struct CombatLog {
+    pub entries: VecDeque<CombatLogEntry>,
+    pub dealt_by_kind: HashMap<AttackKind, f32>,
+    dump: Option<LineWriter<File>>,
+}
+
+impl CombatLog {
+    pub fn new() -> Self {
+        let dump = std::env::var("COMBAT_LOG_PATH").ok().and_then(|path| {
+            File::options()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|err| warn!("Could not open combat log {path}: {err}"))
+                .ok()
+                .map(LineWriter::new)
+        });
+        Self { dump, ..default() }
+    }
+
+    pub fn record(&mut self, entry: CombatLogEntry) {
+        if entry.is_player_damage() {
+            *self.dealt_by_kind.entry(entry.kind).or_default() += entry.mitigated;
+        }
+        if let Some(dump) = self.dump.as_mut() {
+            if writeln!(dump, "{}", entry.to_json()).is_err() {
+                self.dump = None;
+            }
+        }
+        if self.entries.len() >= COMBAT_LOG_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn dps_by_kind(&self, now: f32, window: f32) -> Vec<(AttackKind, f32)> {
+        AttackKind::PLAYER_SOURCES
+            .iter()
+            .map(|&kind| {
+                let total: f32 = self
+                    .entries
+                    .iter()
+                    .rev()
+                    .take_while(|e| e.time >= now - window)
+                    .filter(|e| e.kind == kind && e.is_player_damage())
+                    .map(|e| e.mitigated)
+                    .sum();
+                (kind, total / window)
+            })
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.dealt_by_kind.clear();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IncomingHit {
+    pub time: f32,
+    pub attacker: String,
+    pub is_elite: bool,
+    pub is_boss: bool,
+    pub damage_type: DamageType,
+    pub raw: f32,
+    pub mitigated: f32,
+    pub shield_absorbed: f32,
+}
+
+/// Damage the player took over the last `DEATH_RECAP_WINDOW` seconds.
+#[derive(Resource, Default)]
+pub struct DeathRecap {
+    pub hits: VecDeque<IncomingHit>,
+}
+
+impl DeathRecap {
+    pub fn record(&mut self, hit: IncomingHit) {
+        while self
+            .hits
+            .front()
+            .is_some_and(|old| old.time < hit.time - DEATH_RECAP_WINDOW)
+        {
+            self.hits.pop_front();
+        }
+        self.hits.push_back(hit);
+    }
+
+    pub fn killing_blow(&self) -> Option<&IncomingHit> {
+        self.hits.back()
+    }
+
+    /// Attackers sorted by total damage taken from them, with hit counts.
+    pub fn top_sources(&self, count: usize) -> Vec<(String, f32, u32)> {
+        let mut totals: Vec<(String, f32, u32)> = Vec::new();
+        for hit in &self.hits {
+            match totals.iter_mut().find(|(name, ..)| *name == hit.attacker) {
+                Some((_, total, hits)) => {
+                    *total += hit.mitigated;
+                    *hits += 1;
+                }
+                None => totals.push((hit.attacker.clone(), hit.mitigated, 1)),
+            }
+        }
+        totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+        totals.truncate(count);
+        totals
+    }
+
+    pub fn shield_absorbed(&self) -> f32 {
+        self.hits.iter().map(|hit| hit.shield_absorbed).sum()
+    }
+}
+
 #[derive(Resource)]
 pub struct KillFeedback {
     pub recent_kills: u32,
@@ -180,3 +864,17 @@ pub struct BossEntranceActive(pub bool);
 
 #[derive(Resource, Default)]
 pub struct BossDeathActive(pub bool);
+
+#[derive(Resource, Default)]
+pub struct BossRoster {
+    pub bosses: Vec<BossDefinition>,
+}
+
+impl BossRoster {
+    pub fn for_tier(&self, tier: u32) -> Option<&BossDefinition> {
+        self.bosses
+            .iter()
+            .filter(|boss| boss.min_tier <= tier)
+            .max_by_key(|boss| boss.min_tier)
+    }
+}
@@ -0,0 +1,277 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::helpers::*;
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+pub fn roll_elite_affixes(rng: &mut impl Rng, map_tier: u32) -> Vec<EliteAffix> {
+    let max_affixes = (1 + map_tier as usize / 2).min(ELITE_MAX_AFFIXES);
+    let count = rng.gen_range(1..=max_affixes);
+    EliteAffix::ALL
+        .choose_multiple(rng, count)
+        .copied()
+        .collect()
+}
+
+pub fn affix_speed_mult(affixes: &[EliteAffix]) -> f32 {
+    if affixes.contains(&EliteAffix::ExtraFast) {
+        ELITE_EXTRA_FAST_MULT
+    } else {
+        1.0
+    }
+}
+
+pub fn attach_elite_affixes(
+    commands: &mut Commands,
+    entity: Entity,
+    affixes: &[EliteAffix],
+    damage: f32,
+    aura_radius: f32,
+    label_height: f32,
+) {
+    let mut entity_commands = commands.entity(entity);
+    for affix in affixes {
+        match affix {
+            EliteAffix::Vampiric => {
+                entity_commands.insert(Vampiric {
+                    leech_pct: ELITE_VAMPIRIC_LEECH,
+                });
+            }
+            EliteAffix::FrozenAura => {
+                entity_commands.insert((
+                    FrozenAura {
+                        radius: ELITE_FROZEN_AURA_RADIUS,
+                        slow: ELITE_FROZEN_AURA_SLOW,
+                    },
+                    EnemyElement(PassiveEffect::ChanceIce(ELITE_ELEMENT_PROC_CHANCE)),
+                ));
+            }
+            EliteAffix::MoltenTrail => {
+                entity_commands.insert((
+                    MoltenTrail {
+                        drop_timer: Timer::from_seconds(
+                            ELITE_MOLTEN_DROP_INTERVAL,
+                            TimerMode::Repeating,
+                        ),
+                        damage: damage * ELITE_MOLTEN_DAMAGE_MULT,
+                    },
+                    EnemyElement(PassiveEffect::ChanceFire(ELITE_ELEMENT_PROC_CHANCE)),
+                ));
+            }
+            EliteAffix::Teleporter => {
+                entity_commands.insert(Teleporter {
+                    timer: Timer::from_seconds(ELITE_TELEPORT_INTERVAL, TimerMode::Repeating),
+                });
+            }
+            EliteAffix::Shielding => {
+                entity_commands.insert(ShieldingAura {
+                    radius: aura_radius,
+                    timer: Timer::from_seconds(ELITE_SHIELD_INTERVAL, TimerMode::Repeating),
+                });
+            }
+            EliteAffix::ExtraFast => {}
+        }
+    }
+
+    let label = affixes
+        .iter()
+        .map(|a| a.name())
+        .collect::<Vec<_>>()
+        .join(" - ");
+
+    entity_commands.with_children(|parent| {
+        parent.spawn(Text2dBundle {
+            text: Text::from_section(
+                label,
+                TextStyle {
+                    font_size: 18.0,
+                    color: Color::srgb(1.0, 0.75, 0.3),
+                    ..default()
+                },
+            ),
+            transform: Transform::from_xyz(0.0, label_height, 0.3),
+            ..default()
+        });
+    });
+}
+
+pub fn frozen_aura_slow(
+    mut commands: Commands,
+    player_query: Query<(Entity, &Transform, Option<&AuraSlow>), With<Player>>,
+    auras: Query<(&Transform, &FrozenAura), Without<Player>>,
+) {
+    let Ok((player_entity, player_transform, current)) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+
+    let slow = auras
+        .iter()
+        .filter(|(t, aura)| in_range(t.translation.truncate(), player_pos, aura.radius))
+        .map(|(_, aura)| aura.slow)
+        .fold(0.0, f32::max);
+
+    if slow > 0.0 {
+        if current.map(|s| s.0) != Some(slow) {
+            commands.entity(player_entity).insert(AuraSlow(slow));
+        }
+    } else if current.is_some() {
+        commands.entity(player_entity).remove::<AuraSlow>();
+    }
+}
+
+pub fn molten_trail_drop(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut trails: Query<(Entity, &Transform, &mut MoltenTrail)>,
+) {
+    for (entity, transform, mut trail) in trails.iter_mut() {
+        trail.drop_timer.tick(time.delta());
+        if !trail.drop_timer.just_finished() {
+            continue;
+        }
+
+        commands.spawn((
+            MoltenPool {
+                owner: entity,
+                damage: trail.damage,
+                radius: ELITE_MOLTEN_POOL_RADIUS,
+                lifetime: Timer::from_seconds(ELITE_MOLTEN_POOL_LIFETIME, TimerMode::Once),
+                tick: Timer::from_seconds(ELITE_MOLTEN_TICK, TimerMode::Repeating),
+            },
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgba(1.0, 0.35, 0.0, 0.5),
+                    custom_size: Some(Vec2::splat(ELITE_MOLTEN_POOL_RADIUS * 2.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    transform.translation.truncate().extend(1.0),
+                ),
+                ..default()
+            },
+        ));
+    }
+}
+
+pub fn update_molten_pools(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pools: Query<(Entity, &Transform, &mut MoltenPool, &mut Sprite)>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
+) {
+    let player = player_query.get_single().ok();
+
+    for (entity, transform, mut pool, mut sprite) in pools.iter_mut() {
+        pool.lifetime.tick(time.delta());
+        pool.tick.tick(time.delta());
+
+        if pool.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        sprite
+            .color
+            .set_alpha(0.5 * (1.0 - pool.lifetime.fraction()));
+
+        if !pool.tick.just_finished() {
+            continue;
+        }
+        let Some((player_entity, player_transform)) = player else {
+            continue;
+        };
+        if in_range(
+            transform.translation.truncate(),
+            player_transform.translation.truncate(),
+            pool.radius + PLAYER_RADIUS,
+        ) {
+            damage_events.send(DamageEvent {
+                target: player_entity,
+                attacker: Some(pool.owner),
+                amount: pool.damage,
+                damage_type: DamageType::Fire,
+                source: DamageSource::DoT,
+                kind: AttackKind::Enemy,
+                crit_bonus: 0.0,
+            });
+            status_events.send(ApplyStatusEvent {
+                target: player_entity,
+                effect: PassiveEffect::ChanceFire(1.0),
+                applier: Some(pool.owner),
+            });
+        }
+    }
+}
+
+pub fn elite_teleport(
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut teleporters: Query<
+        (&mut Transform, &mut Teleporter),
+        (
+            Without<Player>,
+            Without<EnemyWindup>,
+            Without<EnemyRecovery>,
+            Without<Frozen>,
+        ),
+    >,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+    let mut rng = rand::thread_rng();
+
+    for (mut transform, mut teleporter) in teleporters.iter_mut() {
+        teleporter.timer.tick(time.delta());
+        if !teleporter.timer.just_finished() {
+            continue;
+        }
+        if in_range(
+            transform.translation.truncate(),
+            player_pos,
+            ELITE_TELEPORT_MIN_DISTANCE,
+        ) {
+            continue;
+        }
+
+        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+        let target = player_pos + Vec2::from_angle(angle) * rng.gen_range(120.0..180.0);
+        let target = clamp_to_bounds(target, MAP_BOUNDS);
+        transform.translation = target.extend(transform.translation.z);
+    }
+}
+
+pub fn elite_shielding(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut shielders: Query<(Entity, &Transform, &mut ShieldingAura)>,
+    mut allies: Query<(Entity, &Transform, &Health, Option<&mut Shield>), With<Enemy>>,
+) {
+    for (shielder_entity, shielder_transform, mut aura) in shielders.iter_mut() {
+        aura.timer.tick(time.delta());
+        if !aura.timer.just_finished() {
+            continue;
+        }
+        let center = shielder_transform.translation.truncate();
+
+        for (ally_entity, ally_transform, health, shield) in allies.iter_mut() {
+            if ally_entity == shielder_entity
+                || !in_range(ally_transform.translation.truncate(), center, aura.radius)
+            {
+                continue;
+            }
+            let amount = health.max * ELITE_SHIELD_HP_PCT;
+            if let Some(mut shield) = shield {
+                shield.amount = shield.amount.max(amount);
+            } else {
+                commands.entity(ally_entity).try_insert(Shield { amount });
+            }
+        }
+    }
+}
@@ -21,18 +21,31 @@ pub fn update_character_animation_texture(
             &CharacterState,
             Option<&Player>,
             Option<&Enemy>,
+            Option<&MeleeCombo>,
+            Option<&mut AnimationConfig>,
             &mut Handle<Image>,
             &mut TextureAtlas,
         ),
-        Changed<CharacterState>,
+        Or<(Changed<CharacterState>, Changed<MeleeCombo>)>,
     >,
 ) {
-    for (state, player, enemy, mut image, mut atlas) in query.iter_mut() {
+    for (state, player, enemy, combo, config, mut image, mut atlas) in query.iter_mut() {
+        let mut wide = false;
         if player.is_some() {
+            let combo_step = combo
+                .filter(|combo| !combo.window.finished())
+                .map_or(0, |combo| combo.step);
             *image = match state {
                 CharacterState::Idle => sprites.soldier_idle.clone(),
                 CharacterState::Walking => sprites.soldier_walk.clone(),
-                CharacterState::Attacking => sprites.soldier_attack.clone(),
+                CharacterState::Attacking => match combo_step {
+                    0 => sprites.soldier_attack.clone(),
+                    1 => sprites.soldier_attack2.clone(),
+                    _ => {
+                        wide = true;
+                        sprites.soldier_attack3.clone()
+                    }
+                },
                 _ => sprites.soldier_idle.clone(),
             };
         } else if enemy.is_some() {
@@ -43,6 +56,18 @@ pub fn update_character_animation_texture(
                 _ => sprites.orc_idle.clone(),
             };
         }
+        if let Some(mut config) = config {
+            // The third soldier swing is a 9-frame sheet.
+            let (layout, frame_count) = if wide {
+                (&sprites.wide_layout, 9)
+            } else {
+                (&sprites.layout, 6)
+            };
+            if config.frame_count != frame_count {
+                atlas.layout = layout.clone();
+                config.frame_count = frame_count;
+            }
+        }
         atlas.index = 0;
     }
 }
@@ -0,0 +1,273 @@
+use bevy::asset::LoadState;
+use bevy::audio::{AudioSourceBundle, SpatialScale, Volume};
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+
+use crate::components::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::helpers::*;
+use crate::resources::*;
+
+fn effect_placeholder(kind: SoundKind) -> Synth {
+    use Wave::*;
+    Synth::new(match kind {
+        SoundKind::Hit => vec![
+            Tone::new(Noise, 0.0, 0.06, 0.0, 0.5),
+            Tone::new(Square, 0.0, 0.08, 220.0, 0.3).glide_to(110.0),
+        ],
+        SoundKind::Crit => vec![
+            Tone::new(Noise, 0.0, 0.08, 0.0, 0.4),
+            Tone::new(Square, 0.0, 0.12, 660.0, 0.35).glide_to(330.0),
+            Tone::new(Sine, 0.02, 0.18, 1320.0, 0.2),
+        ],
+        SoundKind::Kill => vec![
+            Tone::new(Noise, 0.0, 0.2, 0.0, 0.5),
+            Tone::new(Triangle, 0.0, 0.25, 180.0, 0.6).glide_to(60.0),
+        ],
+        SoundKind::PlayerHurt => vec![
+            Tone::new(Noise, 0.0, 0.1, 0.0, 0.3),
+            Tone::new(Square, 0.0, 0.18, 300.0, 0.4).glide_to(150.0),
+        ],
+        SoundKind::LevelUp => [523.25, 659.25, 783.99, 1046.5]
+            .iter()
+            .enumerate()
+            .map(|(i, &freq)| {
+                Tone::new(Triangle, i as f32 * 0.1, 0.25 + i as f32 * 0.08, freq, 0.4)
+            })
+            .collect(),
+        SoundKind::SkillCast => vec![
+            Tone::new(Sine, 0.0, 0.3, 300.0, 0.4).glide_to(900.0),
+            Tone::new(Noise, 0.0, 0.15, 0.0, 0.15),
+        ],
+        SoundKind::BossDarkening => vec![
+            Tone::new(Sine, 0.0, 1.5, 110.0, 0.5).glide_to(55.0),
+            Tone::new(Triangle, 0.0, 1.5, 55.0, 0.4).glide_to(40.0),
+        ],
+        SoundKind::BossSpawn => vec![
+            Tone::new(Noise, 0.0, 0.8, 0.0, 0.5),
+            Tone::new(Square, 0.0, 1.2, 80.0, 0.5).glide_to(40.0),
+            Tone::new(Triangle, 0.1, 0.9, 160.0, 0.3).glide_to(80.0),
+        ],
+        SoundKind::Pickup => vec![
+            Tone::new(Sine, 0.0, 0.08, 880.0, 0.3),
+            Tone::new(Sine, 0.06, 0.08, 1320.0, 0.3),
+        ],
+        SoundKind::Shrine => [440.0, 554.37, 659.25]
+            .iter()
+            .map(|&freq| Tone::new(Triangle, 0.0, 1.0, freq, 0.25))
+            .collect(),
+    })
+}
+
+/// Every layer loops over `MUSIC_LOOP_LENGTH` at 120 bpm, so they stay in step when faded in.
+fn music_placeholder(kind: MusicLayerKind) -> Synth {
+    use Wave::*;
+    let beats = (0..(MUSIC_LOOP_LENGTH * 2.0) as usize).map(|i| i as f32 * 0.5);
+    let tones = match kind {
+        MusicLayerKind::Base => {
+            let bass = [110.0, 110.0, 130.81, 110.0, 87.31, 87.31, 98.0, 82.41];
+            beats
+                .zip(bass)
+                .map(|(start, freq)| Tone::new(Triangle, start, 0.45, freq, 0.5))
+                .collect()
+        }
+        MusicLayerKind::Horde => beats
+            .flat_map(|start| {
+                [
+                    Tone::new(Sine, start, 0.15, 120.0, 0.7).glide_to(50.0),
+                    Tone::new(Noise, start + 0.25, 0.05, 0.0, 0.25),
+                ]
+            })
+            .collect(),
+        MusicLayerKind::Boss => beats
+            .flat_map(|start| {
+                [
+                    Tone::new(Square, start, 0.2, 55.0, 0.25),
+                    Tone::new(Square, start + 0.25, 0.2, 55.0, 0.2),
+                ]
+            })
+            .chain([
+                Tone::new(Triangle, 0.0, 1.5, 220.0, 0.3),
+                Tone::new(Triangle, 2.0, 1.5, 233.08, 0.3),
+            ])
+            .collect(),
+    };
+    Synth::new(tones).with_length(MUSIC_LOOP_LENGTH)
+}
+
+pub fn setup_audio(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut synths: ResMut<Assets<Synth>>,
+) {
+    let effects = SoundKind::ALL
+        .iter()
+        .map(|&kind| {
+            let sources = SoundSources {
+                file: asset_server.load(kind.path()),
+                placeholder: synths.add(effect_placeholder(kind)),
+            };
+            (kind, sources)
+        })
+        .collect();
+    let music = MusicLayerKind::ALL
+        .iter()
+        .map(|&kind| {
+            let sources = SoundSources {
+                file: asset_server.load(kind.path()),
+                placeholder: synths.add(music_placeholder(kind)),
+            };
+            (kind, sources)
+        })
+        .collect();
+    commands.insert_resource(SoundLibrary { effects, music });
+
+    for kind in MusicLayerKind::ALL {
+        let volume = if kind == MusicLayerKind::Base {
+            1.0
+        } else {
+            0.0
+        };
+        commands.spawn(MusicLayer { kind, volume });
+    }
+}
+
+/// Music layers start once their file has either loaded or failed, falling back to the
+/// generated stem so a missing file never leaves the game silent.
+pub fn start_music_layers(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    library: Res<SoundLibrary>,
+    layers: Query<(Entity, &MusicLayer), (Without<Handle<AudioSource>>, Without<Handle<Synth>>)>,
+) {
+    for (entity, layer) in layers.iter() {
+        let Some(sources) = library.music.get(&layer.kind) else {
+            continue;
+        };
+        let settings = PlaybackSettings::LOOP.with_volume(Volume::new(0.0));
+        match asset_server.load_state(&sources.file) {
+            LoadState::Loaded => {
+                commands.entity(entity).insert(AudioBundle {
+                    source: sources.file.clone(),
+                    settings,
+                });
+            }
+            LoadState::Failed(_) => {
+                commands.entity(entity).insert(AudioSourceBundle::<Synth> {
+                    source: sources.placeholder.clone(),
+                    settings,
+                });
+            }
+            LoadState::NotLoaded | LoadState::Loading => {}
+        }
+    }
+}
+
+pub fn attach_spatial_listener(
+    mut commands: Commands,
+    cameras: Query<Entity, (With<Camera2d>, Without<SpatialListener>)>,
+) {
+    for entity in cameras.iter() {
+        commands
+            .entity(entity)
+            .insert(SpatialListener::new(AUDIO_EAR_GAP));
+    }
+}
+
+pub fn play_sound_effects(
+    mut commands: Commands,
+    mut sound_events: EventReader<SoundEvent>,
+    asset_server: Res<AssetServer>,
+    library: Res<SoundLibrary>,
+    settings: Res<Settings>,
+    voices: Query<&SoundVoice>,
+) {
+    if settings.volume <= 0.0 {
+        sound_events.clear();
+        return;
+    }
+
+    let mut playing: HashMap<SoundKind, usize> = HashMap::new();
+    for voice in voices.iter() {
+        *playing.entry(voice.0).or_default() += 1;
+    }
+
+    let mut rng = rand::thread_rng();
+    for event in sound_events.read() {
+        let count = playing.entry(event.kind).or_default();
+        if *count >= event.kind.max_voices() {
+            continue;
+        }
+        let Some(sources) = library.effects.get(&event.kind) else {
+            continue;
+        };
+        *count += 1;
+
+        let variation = event.kind.pitch_variation();
+        let speed = if variation > 0.0 {
+            1.0 + rng.gen_range(-variation..variation)
+        } else {
+            1.0
+        };
+        let mut settings = PlaybackSettings::DESPAWN
+            .with_volume(Volume::new(event.kind.volume() * settings.volume))
+            .with_speed(speed);
+        if event.position.is_some() {
+            settings = settings
+                .with_spatial(true)
+                .with_spatial_scale(SpatialScale::new_2d(AUDIO_SPATIAL_SCALE));
+        }
+
+        let mut voice = if asset_server.is_loaded_with_dependencies(&sources.file) {
+            commands.spawn(AudioBundle {
+                source: sources.file.clone(),
+                settings,
+            })
+        } else {
+            commands.spawn(AudioSourceBundle::<Synth> {
+                source: sources.placeholder.clone(),
+                settings,
+            })
+        };
+        voice.insert(SoundVoice(event.kind));
+        if let Some(position) = event.position {
+            voice.insert(SpatialBundle::from_transform(Transform::from_translation(
+                position.extend(0.0),
+            )));
+        }
+    }
+}
+
+pub fn update_music_layers(
+    time: Res<Time<Real>>,
+    settings: Res<Settings>,
+    state: Res<State<GameState>>,
+    horde_wave: Res<HordeWaveActive>,
+    boss_entrance: Res<BossEntranceActive>,
+    bosses: Query<(), With<Boss>>,
+    mut layers: Query<(&mut MusicLayer, Option<&AudioSink>)>,
+) {
+    let in_run = matches!(
+        state.get(),
+        GameState::Playing | GameState::Paused | GameState::LevelUp
+    );
+    let boss_fight = in_run && (boss_entrance.0 || !bosses.is_empty());
+    let horde = in_run && (horde_wave.active || boss_fight);
+
+    for (mut layer, sink) in layers.iter_mut() {
+        let audible = match layer.kind {
+            MusicLayerKind::Base => true,
+            MusicLayerKind::Horde => horde,
+            MusicLayerKind::Boss => boss_fight,
+        };
+        let target = if audible { 1.0 } else { 0.0 };
+        let step = MUSIC_FADE_SPEED * time.delta_seconds();
+        layer.volume += (target - layer.volume).clamp(-step, step);
+
+        if let Some(sink) = sink {
+            sink.set_volume(layer.volume * MUSIC_VOLUME * settings.volume);
+        }
+    }
+}
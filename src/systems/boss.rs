@@ -0,0 +1,440 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::helpers::*;
+use crate::resources::*;
+use crate::systems::enemy::spawn_basic_enemy;
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+use rand::Rng;
+use std::collections::HashSet;
+
+pub fn update_boss_phases(
+    mut commands: Commands,
+    mut bosses: Query<(Entity, &Transform, &Health, &mut BossBrain, &mut Enemy)>,
+    mut text_events: EventWriter<FloatingTextEvent>,
+    mut sound_events: EventWriter<SoundEvent>,
+) {
+    for (entity, transform, health, mut brain, mut enemy) in bosses.iter_mut() {
+        let next_phase = brain.phase + 1;
+        let Some(phase) = brain.definition.phases.get(next_phase) else {
+            continue;
+        };
+        if health.current / health.max > phase.hp_threshold {
+            continue;
+        }
+
+        brain.phase = next_phase;
+        brain.pattern_index = 0;
+        let interval = brain.pattern_interval();
+        brain.pattern_timer = Timer::from_seconds(interval, TimerMode::Once);
+        brain.apply_multipliers(&mut enemy);
+
+        commands
+            .entity(entity)
+            .try_insert(Invulnerable(Timer::from_seconds(
+                BOSS_PHASE_TRANSITION_DURATION,
+                TimerMode::Once,
+            )));
+
+        commands.spawn((
+            BossPhaseTransition {
+                timer: Timer::from_seconds(BOSS_PHASE_TRANSITION_DURATION, TimerMode::Once),
+            },
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgba(0.0, 0.0, 0.0, 0.0),
+                    custom_size: Some(Vec2::splat(5000.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(Vec3::new(0.0, 0.0, 50.0)),
+                ..default()
+            },
+        ));
+        sound_events.send(SoundEvent {
+            kind: SoundKind::BossDarkening,
+            position: None,
+        });
+
+        text_events.send(FloatingTextEvent::announcement(
+            format!("PHASE {}", next_phase + 1),
+            Color::srgb(1.0, 0.4, 0.2),
+            transform.translation + Vec3::new(0.0, 150.0, 100.0),
+        ));
+    }
+}
+
+/// Darkens the screen and lifts it again over the boss's invulnerable phase change.
+pub fn update_boss_phase_transitions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut transitions: Query<(Entity, &mut BossPhaseTransition, &mut Sprite)>,
+) {
+    for (entity, mut transition, mut sprite) in transitions.iter_mut() {
+        transition.timer.tick(time.delta());
+        let t = transition.timer.fraction();
+        sprite.color = Color::srgba(0.0, 0.0, 0.0, 0.6 * (1.0 - (2.0 * t - 1.0).abs()));
+        if transition.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn update_boss_enrage(
+    time: Res<Time>,
+    mut bosses: Query<(
+        &Transform,
+        &mut BossBrain,
+        &mut Enemy,
+        &mut Sprite,
+        Option<&mut ElementalStatus>,
+    )>,
+    mut text_events: EventWriter<FloatingTextEvent>,
+) {
+    for (transform, mut brain, mut enemy, mut sprite, status) in bosses.iter_mut() {
+        brain.enrage_timer.tick(time.delta());
+        if !brain.enrage_timer.just_finished() || brain.enraged {
+            continue;
+        }
+
+        brain.enraged = true;
+        brain.apply_multipliers(&mut enemy);
+        sprite.color = Color::srgb(0.9, 0.05, 0.05);
+        if let Some(mut status) = status {
+            status.base_color = Some(sprite.color);
+        }
+
+        text_events.send(FloatingTextEvent::announcement(
+            "ENRAGED!",
+            Color::srgb(1.0, 0.1, 0.1),
+            transform.translation + Vec3::new(0.0, 150.0, 100.0),
+        ));
+    }
+}
+
+pub fn run_boss_patterns(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+    sprites: Res<CharacterSprites>,
+    player_query: Query<(&Transform, &Level), With<Player>>,
+    mut bosses: Query<
+        (Entity, &Transform, &Sprite, &Enemy, &mut BossBrain),
+        (Without<BossCharge>, Without<EnemyWindup>, Without<Frozen>),
+    >,
+) {
+    let Ok((player_transform, level)) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+    let mut rng = rand::thread_rng();
+
+    for (entity, transform, sprite, enemy, mut brain) in bosses.iter_mut() {
+        brain.pattern_timer.tick(time.delta());
+        if !brain.pattern_timer.finished() {
+            continue;
+        }
+
+        let patterns = &brain.current_phase().patterns;
+        if patterns.is_empty() {
+            continue;
+        }
+        let pattern = patterns[brain.pattern_index % patterns.len()];
+        brain.pattern_index += 1;
+        let interval = brain.pattern_interval();
+        brain.pattern_timer = Timer::from_seconds(interval, TimerMode::Once);
+
+        let boss_pos = transform.translation.truncate();
+        let boss_size = sprite.custom_size.unwrap_or(Vec2::splat(400.0)).x;
+
+        match pattern {
+            BossPattern::Slam {
+                radius,
+                damage_mult,
+            } => {
+                spawn_ground_telegraph(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    entity,
+                    player_pos,
+                    radius,
+                    enemy.damage * damage_mult,
+                    BOSS_SLAM_WINDUP,
+                );
+            }
+            BossPattern::Charge { speed, duration } => {
+                let direction = direction_to(boss_pos, player_pos);
+                let length = speed * duration;
+                commands.entity(entity).insert(BossCharge {
+                    windup: Timer::from_seconds(BOSS_CHARGE_WINDUP, TimerMode::Once),
+                    direction,
+                    speed,
+                    duration,
+                    started: false,
+                    hit_player: false,
+                });
+                let full_size = Vec2::new(length, boss_size * 0.4);
+                commands.spawn((
+                    AttackTelegraph {
+                        owner: entity,
+                        full_size,
+                    },
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::srgba(1.0, 0.15, 0.1, 0.0),
+                            custom_size: Some(full_size * 0.2),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(
+                            (boss_pos + direction * length / 2.0).extend(1.5),
+                        )
+                        .with_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))),
+                        ..default()
+                    },
+                ));
+            }
+            BossPattern::ProjectileRing { count, speed } => {
+                let offset = rng.gen_range(0.0..std::f32::consts::TAU);
+                for i in 0..count {
+                    let angle = offset + (i as f32 / count as f32) * std::f32::consts::TAU;
+                    let direction = Vec2::from_angle(angle);
+                    commands.spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color: Color::srgb(1.0, 0.3, 0.1),
+                                custom_size: Some(Vec2::splat(22.0)),
+                                ..default()
+                            },
+                            transform: Transform::from_translation(
+                                (boss_pos + direction * boss_size * 0.25).extend(5.0),
+                            ),
+                            ..default()
+                        },
+                        EnemyProjectile {
+                            damage: enemy.damage * 0.5,
+                            owner: entity,
+                        },
+                        Velocity(direction * speed),
+                        Lifetime(Timer::from_seconds(
+                            ENEMY_PROJECTILE_LIFETIME,
+                            TimerMode::Once,
+                        )),
+                    ));
+                }
+            }
+            BossPattern::SummonAdds { count } => {
+                for _ in 0..count {
+                    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                    let spawn_pos = clamp_to_bounds(
+                        boss_pos + Vec2::from_angle(angle) * boss_size * 0.6,
+                        MAP_BOUNDS,
+                    );
+                    spawn_basic_enemy(
+                        &mut commands,
+                        &sprites,
+                        spawn_pos,
+                        rng.gen_range(0..3),
+                        level.level,
+                    );
+                }
+            }
+            BossPattern::ArenaHazards { count, radius } => {
+                for _ in 0..count {
+                    let offset =
+                        Vec2::new(rng.gen_range(-400.0..400.0), rng.gen_range(-400.0..400.0));
+                    spawn_ground_telegraph(
+                        &mut commands,
+                        &mut meshes,
+                        &mut materials,
+                        entity,
+                        clamp_to_bounds(player_pos + offset, MAP_BOUNDS),
+                        radius,
+                        enemy.damage * 0.75,
+                        BOSS_HAZARD_WINDUP,
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn spawn_ground_telegraph(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    owner: Entity,
+    position: Vec2,
+    radius: f32,
+    damage: f32,
+    windup: f32,
+) {
+    commands.spawn((
+        GroundTelegraph {
+            owner,
+            damage,
+            radius,
+            timer: Timer::from_seconds(windup, TimerMode::Once),
+        },
+        MaterialMesh2dBundle {
+            mesh: meshes.add(Circle::new(radius)).into(),
+            material: materials.add(Color::srgba(1.0, 0.2, 0.05, 0.15)),
+            transform: Transform::from_translation(position.extend(1.5))
+                .with_scale(Vec3::splat(0.2)),
+            ..default()
+        },
+    ));
+}
+
+pub fn update_ground_telegraphs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    owners: Query<(), With<Enemy>>,
+    mut telegraphs: Query<(
+        Entity,
+        &mut Transform,
+        &mut GroundTelegraph,
+        &Handle<ColorMaterial>,
+    )>,
+) {
+    for (entity, mut transform, mut telegraph, material) in telegraphs.iter_mut() {
+        if owners.get(telegraph.owner).is_err() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        telegraph.timer.tick(time.delta());
+        let t = telegraph.timer.fraction();
+        transform.scale = Vec3::splat(0.2 + 0.8 * t);
+        if let Some(material) = materials.get_mut(material) {
+            material.color = material.color.with_alpha(0.15 + 0.4 * t);
+        }
+
+        if telegraph.timer.finished() {
+            commands.spawn((
+                EnemyHitbox {
+                    damage: telegraph.damage,
+                    owner: telegraph.owner,
+                    radius: telegraph.radius,
+                    lifetime: Timer::from_seconds(ENEMY_HITBOX_LIFETIME, TimerMode::Once),
+                    hit_entities: HashSet::new(),
+                    effect: None,
+                },
+                SpatialBundle::from_transform(Transform::from_translation(transform.translation)),
+            ));
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn update_boss_charge(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: Query<(Entity, &Transform, Option<&Invulnerable>), With<Player>>,
+    mut bosses: Query<
+        (
+            Entity,
+            &Transform,
+            &Sprite,
+            &Enemy,
+            &mut BossCharge,
+            Option<&Dash>,
+        ),
+        Without<Player>,
+    >,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let player = player_query.get_single().ok();
+    for (entity, transform, sprite, enemy, mut charge, dash) in bosses.iter_mut() {
+        if !charge.started {
+            charge.windup.tick(time.delta());
+            if charge.windup.finished() {
+                charge.started = true;
+                commands.entity(entity).insert(Dash {
+                    direction: charge.direction,
+                    speed: charge.speed,
+                    duration: Timer::from_seconds(charge.duration, TimerMode::Once),
+                });
+            }
+            continue;
+        }
+        if dash.is_none() {
+            commands.entity(entity).remove::<BossCharge>();
+            continue;
+        }
+
+        let Some((player_entity, player_transform, invuln)) = player else {
+            continue;
+        };
+        if charge.hit_player || invuln.is_some() {
+            continue;
+        }
+        let contact_radius = sprite.custom_size.unwrap_or(Vec2::splat(400.0)).x * 0.25;
+        if in_range(
+            transform.translation.truncate(),
+            player_transform.translation.truncate(),
+            contact_radius + PLAYER_RADIUS,
+        ) {
+            charge.hit_player = true;
+            damage_events.send(DamageEvent {
+                target: player_entity,
+                attacker: Some(entity),
+                amount: enemy.damage * 1.2,
+                damage_type: DamageType::Physical,
+                source: DamageSource::Hit,
+                kind: AttackKind::Enemy,
+                crit_bonus: 0.0,
+            });
+        }
+    }
+}
+
+pub fn update_enemy_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectiles: Query<
+        (
+            Entity,
+            &mut Transform,
+            &Velocity,
+            &mut Lifetime,
+            &EnemyProjectile,
+        ),
+        Without<Player>,
+    >,
+    player_query: Query<(Entity, &Transform, Option<&Invulnerable>), With<Player>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let player = player_query.get_single().ok();
+    for (entity, mut transform, velocity, mut lifetime, projectile) in projectiles.iter_mut() {
+        transform.translation += (velocity.0 * time.delta_seconds()).extend(0.0);
+        lifetime.0.tick(time.delta());
+        if lifetime.0.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let Some((player_entity, player_transform, invuln)) = player else {
+            continue;
+        };
+        if invuln.is_some() {
+            continue;
+        }
+        if in_range(
+            transform.translation.truncate(),
+            player_transform.translation.truncate(),
+            ENEMY_PROJECTILE_HIT_RADIUS + PLAYER_RADIUS,
+        ) {
+            damage_events.send(DamageEvent {
+                target: player_entity,
+                attacker: Some(projectile.owner),
+                amount: projectile.damage,
+                damage_type: DamageType::Physical,
+                source: DamageSource::Hit,
+                kind: AttackKind::Enemy,
+                crit_bonus: 0.0,
+            });
+            commands.entity(entity).despawn();
+        }
+    }
+}
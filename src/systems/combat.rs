@@ -1,25 +1,34 @@
 use crate::components::*;
 use crate::constants::*;
 use crate::events::*;
+use crate::helpers::*;
 use crate::resources::*;
 use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
 use rand::Rng;
 use std::collections::HashSet;
+use std::f32::consts::FRAC_PI_2;
 
 pub fn update_projectiles(
     mut commands: Commands,
     time: Res<Time>,
-    mut projectiles: Query<(
-        Entity,
-        &mut Transform,
-        &mut Velocity,
-        &mut Lifetime,
-        &mut Projectile,
-    )>,
+    mut projectiles: Query<
+        (
+            Entity,
+            &mut Transform,
+            &Velocity,
+            &mut Lifetime,
+            &mut Projectile,
+            Has<Orbit>,
+        ),
+        Without<Obstacle>,
+    >,
     enemies: Query<(Entity, &Transform), (With<Enemy>, Without<Projectile>)>,
+    obstacles: Query<(&Transform, &Sprite), (With<Obstacle>, Without<Projectile>)>,
     mut damage_events: EventWriter<DamageEvent>,
+    mut hit_events: EventWriter<ProjectileHitEvent>,
 ) {
-    for (proj_entity, mut transform, mut velocity, mut lifetime, mut projectile) in
+    for (proj_entity, mut transform, velocity, mut lifetime, mut projectile, orbiting) in
         projectiles.iter_mut()
     {
         transform.translation += (velocity.0 * time.delta_seconds()).extend(0.0);
@@ -29,6 +38,20 @@ pub fn update_projectiles(
             continue;
         }
         let proj_pos = transform.translation.truncate();
+        if !orbiting
+            && obstacles.iter().any(|(obs_transform, obs_sprite)| {
+                let obs_size = obs_sprite.custom_size.unwrap_or(Vec2::splat(50.0)) / 2.0;
+                point_rect_collision(
+                    proj_pos,
+                    obs_transform.translation.truncate(),
+                    obs_size,
+                    4.0,
+                )
+            })
+        {
+            commands.entity(proj_entity).despawn();
+            continue;
+        }
         let mut target_enemy = None;
         for (enemy_entity, enemy_transform) in enemies.iter() {
             if projectile.hit_entities.contains(&enemy_entity) {
@@ -47,37 +70,20 @@ pub fn update_projectiles(
                 target: enemy_entity,
                 attacker: Some(projectile.owner),
                 amount: projectile.damage,
-                is_crit: projectile.is_crit,
+                damage_type: DamageType::Physical,
+                source: DamageSource::Hit,
+                kind: projectile.kind,
+                crit_bonus: projectile.crit_bonus,
             });
-            let mut chained = false;
-            if projectile.chain_count > 0 {
-                let mut nearest_enemy = None;
-                let mut min_dist = 200.0;
-                for (other_enemy, other_transform) in enemies.iter() {
-                    if projectile.hit_entities.contains(&other_enemy) {
-                        continue;
-                    }
-                    let dist = proj_pos.distance(other_transform.translation.truncate());
-                    if dist < min_dist {
-                        min_dist = dist;
-                        nearest_enemy = Some(other_transform.translation.truncate());
-                    }
-                }
-                if let Some(target_pos) = nearest_enemy {
-                    let new_dir = (target_pos - proj_pos).normalize_or_zero();
-                    let speed = velocity.0.length();
-                    velocity.0 = new_dir * speed;
-                    transform.rotation = Quat::from_rotation_z(new_dir.y.atan2(new_dir.x));
-                    projectile.chain_count -= 1;
-                    chained = true;
-                }
-            }
-            if !chained {
-                if projectile.pierce == 0 {
-                    commands.entity(proj_entity).despawn();
-                } else {
-                    projectile.pierce -= 1;
-                }
+            hit_events.send(ProjectileHitEvent {
+                projectile: proj_entity,
+                target: enemy_entity,
+                position: proj_pos,
+            });
+            if projectile.pierce == 0 {
+                projectile.spent = true;
+            } else {
+                projectile.pierce -= 1;
             }
         }
     }
@@ -102,14 +108,19 @@ pub fn update_melee_attacks(
                 continue;
             }
             let enemy_pos = enemy_transform.translation.truncate();
-            let distance = melee_pos.distance(enemy_pos);
-            if distance < MELEE_HIT_RADIUS {
+            if melee
+                .shape
+                .contains(melee_pos, melee.direction, enemy_pos, MELEE_HIT_PADDING)
+            {
                 melee.hit_entities.insert(enemy_entity);
                 damage_events.send(DamageEvent {
                     target: enemy_entity,
                     attacker: Some(melee.owner),
                     amount: melee.damage,
-                    is_crit: melee.is_crit,
+                    damage_type: DamageType::Physical,
+                    source: DamageSource::Hit,
+                    kind: AttackKind::Primary,
+                    crit_bonus: melee.crit_bonus,
                 });
             }
         }
@@ -119,36 +130,67 @@ pub fn update_melee_attacks(
 pub fn update_aoe_effects(
     mut commands: Commands,
     time: Res<Time>,
-    mut aoe_effects: Query<(Entity, &Transform, &mut AoeEffect, &mut Sprite)>,
+    mut aoe_effects: Query<(
+        Entity,
+        &Transform,
+        &mut AoeEffect,
+        Option<&mut Sprite>,
+        Option<&Handle<ColorMaterial>>,
+        Option<&AoeShape>,
+        Has<Channeled>,
+    )>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
     enemies: Query<(Entity, &Transform), With<Enemy>>,
     mut damage_events: EventWriter<DamageEvent>,
 ) {
-    for (aoe_entity, transform, mut aoe, mut sprite) in aoe_effects.iter_mut() {
+    for (aoe_entity, transform, mut aoe, mut sprite, material, shape, channeled) in
+        aoe_effects.iter_mut()
+    {
         aoe.duration.tick(time.delta());
         aoe.tick_timer.tick(time.delta());
         if aoe.duration.finished() {
             commands.entity(aoe_entity).despawn();
             continue;
         }
-        let alpha = 0.45 * (1.0 - aoe.duration.fraction());
-        sprite.color = sprite.color.with_alpha(alpha);
+        if !channeled {
+            let alpha = 0.45 * (1.0 - aoe.duration.fraction());
+            if let Some(sprite) = sprite.as_mut() {
+                sprite.color = sprite.color.with_alpha(alpha);
+            }
+            if let Some(material) = material.and_then(|handle| materials.get_mut(handle)) {
+                material.color = material.color.with_alpha(alpha);
+            }
+        }
         if aoe.tick_timer.just_finished() {
             aoe.hit_this_tick.clear();
             let aoe_pos = transform.translation.truncate();
-            let radius = sprite.custom_size.unwrap_or(Vec2::splat(100.0)).x / 2.0;
+            let radius = sprite
+                .as_ref()
+                .and_then(|sprite| sprite.custom_size)
+                .unwrap_or(Vec2::splat(100.0))
+                .x
+                / 2.0;
             for (enemy_entity, enemy_transform) in enemies.iter() {
                 if aoe.hit_this_tick.contains(&enemy_entity) {
                     continue;
                 }
                 let enemy_pos = enemy_transform.translation.truncate();
-                let distance = aoe_pos.distance(enemy_pos);
-                if distance < radius {
+                let hit = match shape {
+                    Some(shape) => shape
+                        .shape
+                        .contains(aoe_pos, shape.direction, enemy_pos, 0.0),
+                    None => aoe_pos.distance(enemy_pos) < radius,
+                };
+                if hit {
                     aoe.hit_this_tick.insert(enemy_entity);
                     damage_events.send(DamageEvent {
                         target: enemy_entity,
                         attacker: Some(aoe.owner),
                         amount: aoe.damage,
-                        is_crit: false,
+                        damage_type: aoe.damage_type,
+                        source: DamageSource::Explosion,
+                        kind: aoe.kind,
+                        crit_bonus: aoe.crit_bonus,
                     });
                 }
             }
@@ -156,41 +198,154 @@ pub fn update_aoe_effects(
     }
 }
 
+fn describe_attacker(
+    event: &DamageEvent,
+    archetype: Option<(Option<&EnemySizeTag>, Option<&BossBrain>, bool, bool)>,
+) -> (String, bool, bool) {
+    match (event.kind, archetype) {
+        (_, Some((_, Some(brain), ..))) => (brain.definition.name.clone(), false, true),
+        (_, Some((_, None, _, true))) => ("Mini-Boss".to_string(), true, false),
+        (_, Some((_, None, true, false))) => ("Elite".to_string(), true, false),
+        (AttackKind::Burn, _) => ("Burning".to_string(), false, false),
+        (AttackKind::Hazard, _) => (
+            match event.damage_type {
+                DamageType::Fire => "Fire Pit",
+                DamageType::Cold => "Ice Patch",
+                DamageType::Lightning => "Lightning Pylon",
+                _ => "Hazard",
+            }
+            .to_string(),
+            false,
+            false,
+        ),
+        (_, Some((size, ..))) => {
+            let size = match size.map(|tag| tag.0) {
+                Some(EnemySize::Small) => "Small",
+                Some(EnemySize::Large) => "Large",
+                _ => "Medium",
+            };
+            (format!("{size} Enemy"), false, false)
+        }
+        (_, None) => ("Unknown".to_string(), false, false),
+    }
+}
+
+fn roll_crit(rng: &mut impl Rng, chance: f32, lucky: bool) -> bool {
+    let rolls = if lucky { 2 } else { 1 };
+    (0..rolls).any(|_| rng.r#gen::<f32>() < chance)
+}
+
+pub fn taunt_on_block(
+    mut commands: Commands,
+    mut defense_events: EventReader<DefenseEvent>,
+    passive_tree: Res<PassiveTree>,
+    players: Query<&PlayerPassives, With<Player>>,
+    enemies: Query<(Entity, &Transform), With<Enemy>>,
+) {
+    for event in defense_events.read() {
+        if !matches!(event.outcome, DefenseOutcome::Blocked(_)) {
+            continue;
+        }
+        let Ok(passives) = players.get(event.target) else {
+            continue;
+        };
+        let radius = passive_tree.bonuses(&passives.unlocked_nodes).taunt_radius;
+        if radius <= 0.0 {
+            continue;
+        }
+        let origin = event.position.truncate();
+        for (entity, transform) in enemies.iter() {
+            if in_range(transform.translation.truncate(), origin, radius) {
+                commands
+                    .entity(entity)
+                    .try_insert(Taunted(Timer::from_seconds(
+                        TAUNT_DURATION,
+                        TimerMode::Once,
+                    )));
+            }
+        }
+    }
+}
+
+pub fn update_taunted(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut taunted: Query<(Entity, &mut Taunted)>,
+) {
+    for (entity, mut taunt) in taunted.iter_mut() {
+        taunt.0.tick(time.delta());
+        if taunt.0.finished() {
+            commands.entity(entity).remove::<Taunted>();
+        }
+    }
+}
+
+pub fn mitigate_damage(
+    amount: f32,
+    damage_type: DamageType,
+    stats: Option<&Stats>,
+    status: Option<&ElementalStatus>,
+    armor_mult: f32,
+) -> f32 {
+    let lightning_taken = stats.map_or(1.0, |s| s.lightning_taken());
+    let shock_mult = status.map_or(1.0, |s| 1.0 + s.shock_amp() * lightning_taken);
+
+    let taken = match damage_type {
+        DamageType::Physical => {
+            let mut armor = stats.map_or(0.0, |s| s.armor) * armor_mult;
+            if let Some(s) = status {
+                armor *= 1.0
+                    - (s.fire_stacks as f32 * FIRE_ARMOR_REDUCTION_PER_STACK)
+                        .min(FIRE_MAX_ARMOR_REDUCTION);
+            }
+            1.0 - armor / (armor + 100.0)
+        }
+        DamageType::Fire => stats.map_or(1.0, |s| s.fire_taken()),
+        DamageType::Cold => stats.map_or(1.0, |s| s.cold_taken()),
+        DamageType::Lightning => lightning_taken,
+    };
+
+    amount * taken * shock_mult
+}
+
 pub fn process_damage(
     mut commands: Commands,
     mut damage_events: EventReader<DamageEvent>,
-    mut target_query: Query<
-        (
-            &mut Health,
-            Option<&mut Shield>,
-            &mut Transform,
-            Option<&Stats>,
-            Option<&Invulnerable>,
-            Option<&mut ElementalStatus>,
-            Option<&PlayerPassives>,
-            &Sprite,
-            Option<&EnemySizeTag>,
-            Option<&Boss>,
-        ),
-        Without<Player>,
-    >,
-    mut player_query: Query<
-        (
-            Entity,
-            &mut Health,
-            Option<&mut Shield>,
-            &Transform,
-            &PlayerPassives,
-            &Sprite,
-        ),
-        With<Player>,
-    >,
+    mut target_query: Query<(
+        &mut Health,
+        Option<&mut Shield>,
+        &Transform,
+        Option<&Stats>,
+        Option<&Invulnerable>,
+        Option<&ElementalStatus>,
+        Option<&PlayerPassives>,
+        &Sprite,
+        Option<&EnemySizeTag>,
+        Option<&Boss>,
+        Option<&ShrineBuff>,
+        Has<Player>,
+    )>,
+    attacker_query: Query<(Entity, &Transform, &PlayerPassives, &Stats), With<Player>>,
+    (pet_query, vampire_query): (Query<&Pet>, Query<&Vampiric>),
+    archetype_query: Query<(
+        Option<&EnemySizeTag>,
+        Option<&BossBrain>,
+        Has<Elite>,
+        Has<MiniBoss>,
+    )>,
+    time: Res<Time>,
     mut game_stats: ResMut<GameStats>,
+    mut combat_log: ResMut<CombatLog>,
+    mut death_recap: ResMut<DeathRecap>,
     mut status_events: EventWriter<ApplyStatusEvent>,
+    mut defense_events: EventWriter<DefenseEvent>,
     mut camera_query: Query<(Entity, &mut CameraShake, &OrthographicProjection), With<Camera2d>>,
     mut hit_stop: ResMut<HitStop>,
+    taunted_query: Query<(), With<Taunted>>,
+    (mut sound_events, mut text_events): (EventWriter<SoundEvent>, EventWriter<FloatingTextEvent>),
 ) {
     let mut rng = rand::thread_rng();
+    let mut leeches = Vec::new();
 
     for event in damage_events.read() {
         let mut knockback_info = None;
@@ -198,41 +353,49 @@ pub fn process_damage(
         let mut life_leech_pct = 0.0;
         let mut shield_leech_pct = 0.0;
         let mut damage_mult = 1.0;
-        let mut target_is_player = false;
+        let mut player_attacker = None;
 
         if let Some(attacker_entity) = event.attacker {
-            if let Ok((p_entity, _, _, p_transform, passives, _)) =
-                player_query.get(attacker_entity)
-            {
-                if p_entity == attacker_entity {
-                    if passives.unlocked_nodes.contains(&8) {
-                        knockback_info = Some(p_transform.translation);
-                    }
-                    for &node_id in &passives.unlocked_nodes {
-                        match node_id {
-                            11 => elemental_chances.push(PassiveEffect::ChanceFire(0.20)),
-                            14 => elemental_chances.push(PassiveEffect::ChanceIce(0.25)),
-                            17 => elemental_chances.push(PassiveEffect::ChanceLightning(0.15)),
-                            201 => damage_mult *= 1.25,
-                            204 => damage_mult *= 1.1,
-                            203 => life_leech_pct += 0.015,
-                            104 => shield_leech_pct += 0.03,
-                            _ => {}
+            if let Ok((p_entity, p_transform, passives, _)) = attacker_query.get(attacker_entity) {
+                player_attacker = Some(p_entity);
+                let is_hit = event.source == DamageSource::Hit;
+                if is_hit && passives.unlocked_nodes.contains(&8) {
+                    knockback_info = Some(p_transform.translation);
+                }
+                for &node_id in &passives.unlocked_nodes {
+                    match node_id {
+                        11 if is_hit => elemental_chances.push(PassiveEffect::ChanceFire(0.20)),
+                        14 if is_hit => elemental_chances.push(PassiveEffect::ChanceIce(0.25)),
+                        17 if is_hit => {
+                            elemental_chances.push(PassiveEffect::ChanceLightning(0.15))
                         }
+                        201 => damage_mult *= 1.25,
+                        204 => damage_mult *= 1.1,
+                        203 if is_hit => life_leech_pct += 0.015,
+                        104 if is_hit => shield_leech_pct += 0.03,
+                        _ => {}
                     }
                 }
             }
         }
 
-        let mut final_damage = 0.0;
-        let mut target_transform_pos = Vec3::ZERO;
-        let mut target_sprite_color = Color::WHITE;
-        let target_is_crit = event.is_crit;
+        // Player-owned hits (pets included) crit from the owner's stats; DoTs never crit.
+        let crit_owner = event
+            .attacker
+            .filter(|_| event.source != DamageSource::DoT)
+            .map(|attacker| pet_query.get(attacker).map_or(attacker, |pet| pet.owner))
+            .and_then(|owner| attacker_query.get(owner).ok());
+        let mut target_is_crit = false;
+        if let Some((_, _, passives, stats)) = crit_owner {
+            let lucky = passives.unlocked_nodes.contains(&207);
+            if roll_crit(&mut rng, stats.crit_chance + event.crit_bonus, lucky) {
+                target_is_crit = true;
+                damage_mult *= stats.crit_multiplier;
+            }
+        }
         let mut attack_direction = Vec2::ZERO;
-        let mut enemy_size;
-        let mut will_kill = false;
 
-        if let Ok((
+        let Ok((
             mut health,
             mut shield,
             transform,
@@ -243,74 +406,136 @@ pub fn process_damage(
             sprite,
             size_tag,
             is_boss,
+            shrine_buff,
+            target_is_player,
         )) = target_query.get_mut(event.target)
-        {
-            if invuln.is_some() {
+        else {
+            continue;
+        };
+        if invuln.is_some() {
+            continue;
+        }
+
+        let target_sprite_color = sprite.color;
+        let target_transform_pos = transform.translation;
+        let armor_mult = match target_passives {
+            Some(passives) if passives.unlocked_nodes.contains(&101) => 1.3,
+            _ => 1.0,
+        };
+        if target_is_player && event.attacker.is_some_and(|a| taunted_query.contains(a)) {
+            damage_mult *= 1.0 - TAUNT_DAMAGE_REDUCTION;
+        }
+        let raw_damage = event.amount * damage_mult;
+        let mut incoming = raw_damage;
+        if let (Some(stats), DamageSource::Hit) = (stats, event.source) {
+            let evasion_bonus = match shrine_buff {
+                Some(buff) if buff.buff_type == ShrineType::Evasion => SHRINE_EVASION_BONUS,
+                _ => 0.0,
+            };
+            if rng.r#gen::<f32>() < stats.evade_chance(evasion_bonus) {
+                defense_events.send(DefenseEvent {
+                    target: event.target,
+                    outcome: DefenseOutcome::Evaded,
+                    position: transform.translation,
+                });
                 continue;
             }
-            target_sprite_color = sprite.color;
-            let mut armor = stats.map(|s| s.armor).unwrap_or(0.0);
-            if let Some(passives) = target_passives {
-                if passives.unlocked_nodes.contains(&101) {
-                    armor *= 1.3;
-                }
-            }
-            if let Some(ref s) = status {
-                if s.fire_stacks > 0 {
-                    armor *= 1.0 - (s.fire_stacks as f32 * 0.05).min(0.5);
-                }
+            if rng.r#gen::<f32>() < stats.capped_block_chance() {
+                let blocked = incoming * stats.block_amount.clamp(0.0, 1.0);
+                incoming -= blocked;
+                defense_events.send(DefenseEvent {
+                    target: event.target,
+                    outcome: DefenseOutcome::Blocked(blocked),
+                    position: transform.translation,
+                });
             }
-            let damage_reduction = armor / (armor + 100.0);
-            final_damage = event.amount * damage_mult * (1.0 - damage_reduction);
-            target_transform_pos = transform.translation;
-
-            if let Some(ref mut s) = shield {
-                if s.amount > 0.0 {
-                    if s.amount >= final_damage {
-                        s.amount -= final_damage;
-                    } else {
-                        let rem = final_damage - s.amount;
-                        s.amount = 0.0;
-                        health.current -= rem;
-                    }
+        }
+        let final_damage = mitigate_damage(incoming, event.damage_type, stats, status, armor_mult);
+
+        let was_alive = health.current > 0.0;
+        let mut shield_absorbed = 0.0;
+        if let Some(ref mut s) = shield {
+            if s.amount > 0.0 {
+                if s.amount >= final_damage {
+                    s.amount -= final_damage;
+                    shield_absorbed = final_damage;
                 } else {
-                    health.current -= final_damage;
+                    let rem = final_damage - s.amount;
+                    shield_absorbed = s.amount;
+                    s.amount = 0.0;
+                    health.current -= rem;
                 }
             } else {
                 health.current -= final_damage;
             }
+        } else {
+            health.current -= final_damage;
+        }
 
+        let will_kill = health.current <= 0.0;
+        let entry = CombatLogEntry {
+            time: time.elapsed_seconds(),
+            attacker: event.attacker,
+            target: event.target,
+            kind: event.kind,
+            damage_type: event.damage_type,
+            raw: raw_damage,
+            mitigated: final_damage,
+            is_crit: target_is_crit,
+            killed: was_alive && will_kill,
+            target_is_player,
+        };
+        if target_is_player {
+            game_stats.damage_taken += final_damage;
+            let (attacker, is_elite, is_boss) = describe_attacker(
+                event,
+                event.attacker.and_then(|e| archetype_query.get(e).ok()),
+            );
+            // Hits landing after the lethal one in the same frame must not replace the killing blow.
+            if was_alive {
+                death_recap.record(IncomingHit {
+                    time: entry.time,
+                    attacker,
+                    is_elite,
+                    is_boss,
+                    damage_type: event.damage_type,
+                    raw: raw_damage,
+                    mitigated: final_damage,
+                    shield_absorbed,
+                });
+            }
+            // Vampiric elites only heal from damage that got through to the player's life.
+            if let Some(attacker) = event.attacker
+                && let Ok(vampiric) = vampire_query.get(attacker)
+            {
+                leeches.push((
+                    attacker,
+                    (final_damage - shield_absorbed) * vampiric.leech_pct,
+                ));
+            }
+        } else if entry.is_player_damage() {
+            game_stats.damage_dealt += final_damage;
+        }
+        combat_log.record(entry);
+
+        if !target_is_player {
             for chance_effect in &elemental_chances {
-                match chance_effect {
-                    PassiveEffect::ChanceFire(c) => {
-                        if rng.r#gen::<f32>() < *c {
-                            status_events.send(ApplyStatusEvent {
-                                target: event.target,
-                                effect: *chance_effect,
-                            });
-                        }
-                    }
-                    PassiveEffect::ChanceIce(c) => {
-                        if rng.r#gen::<f32>() < *c {
-                            status_events.send(ApplyStatusEvent {
-                                target: event.target,
-                                effect: *chance_effect,
-                            });
-                        }
-                    }
-                    PassiveEffect::ChanceLightning(c) => {
-                        if rng.r#gen::<f32>() < *c {
-                            status_events.send(ApplyStatusEvent {
-                                target: event.target,
-                                effect: *chance_effect,
-                            });
-                        }
-                    }
-                    _ => {}
+                let chance = match chance_effect {
+                    PassiveEffect::ChanceFire(c)
+                    | PassiveEffect::ChanceIce(c)
+                    | PassiveEffect::ChanceLightning(c) => *c,
+                    _ => 0.0,
+                };
+                if rng.r#gen::<f32>() < chance {
+                    status_events.send(ApplyStatusEvent {
+                        target: event.target,
+                        effect: *chance_effect,
+                        applier: event.attacker,
+                    });
                 }
             }
 
-            enemy_size = if is_boss.is_some() {
+            let enemy_size = if is_boss.is_some() {
                 EnemySize::Boss
             } else if let Some(tag) = size_tag {
                 tag.0
@@ -331,55 +556,30 @@ pub fn process_damage(
                 );
             }
 
-            will_kill = health.current <= 0.0;
-
             commands.entity(event.target).try_insert(LastDamageInfo {
-                was_crit: event.is_crit,
+                was_crit: target_is_crit,
             });
-        } else if let Ok((_e, mut health, mut shield, transform, passives, sprite)) =
-            player_query.get_mut(event.target)
-        {
-            target_is_player = true;
-            target_sprite_color = sprite.color;
-            let mut armor = 0.0;
-            if passives.unlocked_nodes.contains(&101) {
-                armor = 50.0;
-            }
-            let damage_reduction = armor / (armor + 100.0);
-            final_damage = event.amount * (1.0 - damage_reduction);
-            target_transform_pos = transform.translation;
-
-            if let Some(ref mut s) = shield {
-                if s.amount > 0.0 {
-                    if s.amount >= final_damage {
-                        s.amount -= final_damage;
-                    } else {
-                        let rem = final_damage - s.amount;
-                        s.amount = 0.0;
-                        health.current -= rem;
-                    }
-                } else {
-                    health.current -= final_damage;
-                }
-            } else {
-                health.current -= final_damage;
-            }
         }
 
         if final_damage > 0.0 {
-            game_stats.damage_dealt += final_damage;
-
             crate::plugins::game_feel::trigger_damage_flash(
                 &mut commands,
                 event.target,
                 target_sprite_color,
             );
 
+            let sound_position = Some(target_transform_pos.truncate());
             if target_is_player {
                 if let Ok((_, mut shake, _)) = camera_query.get_single_mut() {
                     let trauma = (final_damage / 50.0).min(0.5);
                     crate::plugins::game_feel::add_trauma(&mut shake, trauma);
                 }
+                if event.source != DamageSource::DoT {
+                    sound_events.send(SoundEvent {
+                        kind: SoundKind::PlayerHurt,
+                        position: sound_position,
+                    });
+                }
             } else {
                 let hit_type = if will_kill {
                     HitType::Kill
@@ -389,6 +589,19 @@ pub fn process_damage(
                     HitType::Normal
                 };
 
+                let sound = match hit_type {
+                    HitType::Kill => Some(SoundKind::Kill),
+                    _ if event.source == DamageSource::DoT => None,
+                    HitType::Crit => Some(SoundKind::Crit),
+                    HitType::Normal => Some(SoundKind::Hit),
+                };
+                if let Some(kind) = sound {
+                    sound_events.send(SoundEvent {
+                        kind,
+                        position: sound_position,
+                    });
+                }
+
                 crate::plugins::game_feel::trigger_hit_stop_by_type(&mut hit_stop, hit_type);
 
                 if let Ok((camera_entity, mut shake, projection)) = camera_query.get_single_mut() {
@@ -418,114 +631,151 @@ pub fn process_damage(
                 }
             }
 
-            if let Some(attacker_entity) = event.attacker {
-                if let Ok((p_entity, mut p_health, mut p_shield, _, _, _)) =
-                    player_query.get_single_mut()
+            if let Some(p_entity) = player_attacker {
+                if let Ok((mut p_health, p_shield, p_transform, ..)) =
+                    target_query.get_mut(p_entity)
                 {
-                    if p_entity == attacker_entity {
-                        if life_leech_pct > 0.0 {
-                            p_health.current = (p_health.current + final_damage * life_leech_pct)
-                                .min(p_health.max);
+                    if life_leech_pct > 0.0 {
+                        let before = p_health.current;
+                        p_health.current =
+                            (p_health.current + final_damage * life_leech_pct).min(p_health.max);
+                        if p_health.current > before {
+                            text_events.send(FloatingTextEvent::amount(
+                                FloatingTextStyle::Heal,
+                                p_health.current - before,
+                                Color::srgb(0.3, 1.0, 0.4),
+                                p_transform.translation,
+                                p_entity,
+                            ));
                         }
-                        if let Some(ref mut s) = p_shield {
-                            if shield_leech_pct > 0.0 {
-                                s.amount = (s.amount + final_damage * shield_leech_pct)
-                                    .min(p_health.max * 0.5);
-                            }
+                    }
+                    if let Some(mut s) = p_shield {
+                        if shield_leech_pct > 0.0 {
+                            s.amount = (s.amount + final_damage * shield_leech_pct)
+                                .min(p_health.max * 0.5);
                         }
                     }
                 }
             }
-            let color = if target_is_crit {
-                Color::srgb(1.0, 1.0, 0.0)
+            let (style, color) = if target_is_crit {
+                (FloatingTextStyle::Crit, Color::srgb(1.0, 1.0, 0.0))
             } else {
-                Color::srgb(1.0, 0.3, 0.3)
+                let color = match event.damage_type {
+                    DamageType::Physical => Color::srgb(1.0, 0.3, 0.3),
+                    DamageType::Fire => Color::srgb(1.0, 0.55, 0.1),
+                    DamageType::Cold => Color::srgb(0.5, 0.8, 1.0),
+                    DamageType::Lightning => Color::srgb(0.9, 0.9, 0.5),
+                };
+                (FloatingTextStyle::Damage, color)
             };
-            let font_size = if target_is_crit { 26.0 } else { 18.0 };
-            commands.spawn((
-                Text2dBundle {
-                    text: Text::from_section(
-                        format!("{:.0}", final_damage),
-                        TextStyle {
-                            font_size,
-                            color,
-                            ..default()
-                        },
-                    ),
-                    transform: Transform::from_translation(
-                        target_transform_pos
-                            + Vec3::new(rand::thread_rng().r#gen_range(-15.0..15.0), 20.0, 100.0),
-                    ),
-                    ..default()
-                },
-                DamageNumber {
-                    velocity: Vec2::new(rand::thread_rng().r#gen_range(-25.0..25.0), 60.0),
-                    lifetime: Timer::from_seconds(DAMAGE_NUMBER_LIFETIME, TimerMode::Once),
-                },
-            ));
+            if final_damage > shield_absorbed {
+                text_events.send(FloatingTextEvent::amount(
+                    style,
+                    final_damage - shield_absorbed,
+                    color,
+                    target_transform_pos,
+                    event.target,
+                ));
+            }
+            if shield_absorbed > 0.0 {
+                text_events.send(FloatingTextEvent::amount(
+                    FloatingTextStyle::Shield,
+                    shield_absorbed,
+                    Color::srgb(0.4, 0.7, 1.0),
+                    target_transform_pos,
+                    event.target,
+                ));
+            }
         }
     }
-}
 
-pub fn update_damage_numbers(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut numbers: Query<(Entity, &mut Transform, &mut DamageNumber, &mut Text)>,
-) {
-    for (entity, mut transform, mut number, mut text) in numbers.iter_mut() {
-        number.lifetime.tick(time.delta());
-        if number.lifetime.finished() {
-            commands.entity(entity).despawn();
-            continue;
-        }
-        number.velocity.y -= 120.0 * time.delta_seconds();
-        transform.translation += (number.velocity * time.delta_seconds()).extend(0.0);
-        let alpha = 1.0 - number.lifetime.fraction();
-        for section in text.sections.iter_mut() {
-            section.style.color = section.style.color.with_alpha(alpha);
+    for (attacker, amount) in leeches {
+        if let Ok((mut health, ..)) = target_query.get_mut(attacker) {
+            health.current = (health.current + amount).min(health.max);
         }
     }
 }
 
 pub fn spawn_melee_attack(
     commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
     player_entity: Entity,
     player_pos: Vec2,
     direction: Vec2,
     damage: f32,
-    _attack_speed: f32,
+    step: usize,
     is_tank: bool,
 ) {
-    let mut rng = rand::thread_rng();
-    let is_crit = rng.r#gen::<f32>() < 0.1;
-    let melee_damage = damage * 1.8;
-    let spawn_pos = player_pos + direction * 75.0;
-    let (size, color) = if is_tank {
-        (Vec2::new(220.0, 180.0), Color::srgba(0.2, 0.5, 1.0, 0.7))
+    let (multipliers, scale, color) = if is_tank {
+        (
+            TANK_COMBO_MULTIPLIERS,
+            1.25,
+            Color::srgba(0.2, 0.5, 1.0, 0.7),
+        )
     } else {
-        (Vec2::new(160.0, 120.0), Color::srgba(0.9, 0.4, 0.1, 0.7))
+        (
+            MELEE_COMBO_MULTIPLIERS,
+            1.0,
+            Color::srgba(0.9, 0.4, 0.1, 0.7),
+        )
     };
-    commands.spawn((
-        SpriteBundle {
-            sprite: Sprite {
-                color: if is_crit {
-                    Color::srgba(1.0, 0.6, 0.0, 0.85)
-                } else {
-                    color
-                },
-                custom_size: Some(size),
-                ..default()
-            },
-            transform: Transform::from_translation(spawn_pos.extend(4.0))
-                .with_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))),
-            ..default()
+    let melee_damage = damage * MELEE_DAMAGE_MULT * multipliers[step];
+    let rotation = direction.y.atan2(direction.x);
+
+    // Swings one and two are sweeping arcs, the finisher is a forward thrust.
+    let shape = match step {
+        0 => MeleeShape::Arc {
+            radius: 150.0 * scale,
+            half_angle: 1.0,
         },
-        MeleeAttack {
-            damage: melee_damage,
-            owner: player_entity,
-            duration: Timer::from_seconds(0.12, TimerMode::Once),
-            hit_entities: HashSet::new(),
-            is_crit,
+        1 => MeleeShape::Arc {
+            radius: 150.0 * scale,
+            half_angle: 1.3,
         },
-    ));
+        _ => MeleeShape::Rect {
+            half_extents: Vec2::new(110.0, 45.0) * scale,
+        },
+    };
+    let attack = MeleeAttack {
+        damage: melee_damage,
+        owner: player_entity,
+        duration: Timer::from_seconds(0.12, TimerMode::Once),
+        hit_entities: HashSet::new(),
+        crit_bonus: MELEE_CRIT_BONUS,
+        shape,
+        direction,
+    };
+
+    match shape {
+        MeleeShape::Arc { radius, half_angle } => {
+            // Sectors are built around +Y, so rotate a quarter turn less.
+            commands.spawn((
+                MaterialMesh2dBundle {
+                    mesh: meshes.add(CircularSector::new(radius, half_angle)).into(),
+                    material: materials.add(color),
+                    transform: Transform::from_translation(player_pos.extend(4.0))
+                        .with_rotation(Quat::from_rotation_z(rotation - FRAC_PI_2)),
+                    ..default()
+                },
+                attack,
+            ));
+        }
+        MeleeShape::Rect { half_extents } => {
+            let center = player_pos + direction * half_extents.x;
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(half_extents * 2.0),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(center.extend(4.0))
+                        .with_rotation(Quat::from_rotation_z(rotation)),
+                    ..default()
+                },
+                attack,
+            ));
+        }
+    }
 }
@@ -4,6 +4,7 @@ use crate::events::*;
 use crate::helpers::*;
 use crate::resources::*;
 use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
 use rand::Rng;
 use std::collections::HashSet;
 
@@ -11,14 +12,30 @@ pub fn enemy_ai(
     time: Res<Time>,
     player_query: Query<&Transform, With<Player>>,
     taunt_query: Query<&Transform, (With<Taunt>, Without<Enemy>, Without<Player>)>,
-    mut enemies: Query<(&mut Transform, &Enemy, &mut CharacterState, &mut Sprite), Without<Player>>,
+    mut enemies: Query<
+        (
+            &mut Transform,
+            &Enemy,
+            &mut CharacterState,
+            &mut Sprite,
+            Option<&HazardSlow>,
+            Option<&ElementalStatus>,
+        ),
+        (
+            Without<Player>,
+            Without<EnemyWindup>,
+            Without<EnemyRecovery>,
+            Without<BossCharge>,
+            Without<Frozen>,
+        ),
+    >,
     obstacles: Query<(&Transform, &Sprite), (With<Obstacle>, Without<Player>, Without<Enemy>)>,
 ) {
     let Ok(player_transform) = player_query.get_single() else {
         return;
     };
     let player_pos = player_transform.translation.truncate();
-    for (mut transform, enemy, mut state, mut sprite) in enemies.iter_mut() {
+    for (mut transform, enemy, mut state, mut sprite, slow, status) in enemies.iter_mut() {
         let enemy_pos = transform.translation.truncate();
         let mut target_pos = player_pos;
         let mut min_dist = enemy_pos.distance(player_pos);
@@ -33,7 +50,9 @@ pub fn enemy_ai(
         let distance = to_target.length();
         if distance > ENEMY_STOP_RANGE {
             let direction = to_target.normalize();
-            let movement = direction * enemy.speed * time.delta_seconds();
+            let slow_mult = (1.0 - slow.map(|s| s.0).unwrap_or(0.0))
+                * (1.0 - status.map(|s| s.chill_slow()).unwrap_or(0.0));
+            let movement = direction * enemy.speed * slow_mult * time.delta_seconds();
             let new_pos = transform.translation + movement.extend(0.0);
 
             let mut collision = false;
@@ -65,31 +84,210 @@ pub fn enemy_ai(
 }
 
 pub fn enemy_attack(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
     time: Res<Time>,
-    player_query: Query<(Entity, &Transform), (With<Player>, Without<Enemy>)>,
-    mut enemies: Query<(Entity, &Transform, &mut Enemy, &mut CharacterState)>,
-    mut damage_events: EventWriter<DamageEvent>,
+    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    mut enemies: Query<
+        (
+            Entity,
+            &Transform,
+            &mut Enemy,
+            &mut CharacterState,
+            &mut AnimationConfig,
+            Option<&mut EnemyWindup>,
+            Option<&mut EnemyRecovery>,
+            Option<&EnemyElement>,
+        ),
+        (Without<BossCharge>, Without<Frozen>),
+    >,
 ) {
-    let Ok((player_entity, player_transform)) = player_query.get_single() else {
+    let Ok(player_transform) = player_query.get_single() else {
         return;
     };
     let player_pos = player_transform.translation.truncate();
-    for (enemy_entity, transform, mut enemy, mut state) in enemies.iter_mut() {
+    for (enemy_entity, transform, mut enemy, mut state, mut anim, windup, recovery, element) in
+        enemies.iter_mut()
+    {
+        let enemy_pos = transform.translation.truncate();
+
+        if let Some(mut windup) = windup {
+            windup.timer.tick(time.delta());
+            if windup.timer.finished() {
+                anim.timer = Timer::from_seconds(ENEMY_ATTACK_FRAME_TIME, TimerMode::Repeating);
+                commands.spawn((
+                    EnemyHitbox {
+                        damage: enemy.damage,
+                        owner: enemy_entity,
+                        radius: ENEMY_HITBOX_RADIUS,
+                        lifetime: Timer::from_seconds(ENEMY_HITBOX_LIFETIME, TimerMode::Once),
+                        hit_entities: HashSet::new(),
+                        effect: element.map(|e| e.0),
+                    },
+                    SpatialBundle::from_transform(Transform::from_translation(
+                        (enemy_pos + windup.direction * ENEMY_HITBOX_OFFSET).extend(4.0),
+                    )),
+                ));
+                commands
+                    .entity(enemy_entity)
+                    .remove::<EnemyWindup>()
+                    .insert(EnemyRecovery(Timer::from_seconds(
+                        ENEMY_RECOVERY_DURATION,
+                        TimerMode::Once,
+                    )));
+            }
+            continue;
+        }
+
+        if let Some(mut recovery) = recovery {
+            recovery.0.tick(time.delta());
+            if recovery.0.finished() {
+                *state = CharacterState::Idle;
+                enemy.attack_cooldown.reset();
+                commands.entity(enemy_entity).remove::<EnemyRecovery>();
+            }
+            continue;
+        }
+
         enemy.attack_cooldown.tick(time.delta());
-        if enemy.attack_cooldown.finished() && *state == CharacterState::Attacking {
-            *state = CharacterState::Idle;
+        if enemy.damage <= 0.0 || !enemy.attack_cooldown.finished() {
+            continue;
         }
-        let enemy_pos = transform.translation.truncate();
-        let distance = enemy_pos.distance(player_pos);
-        if distance < ENEMY_ATTACK_RANGE && enemy.attack_cooldown.finished() {
-            enemy.attack_cooldown = Timer::from_seconds(1.0, TimerMode::Once);
-            *state = CharacterState::Attacking;
+        if !in_range(enemy_pos, player_pos, ENEMY_ATTACK_RANGE) {
+            continue;
+        }
+
+        let direction = direction_to(enemy_pos, player_pos);
+        *state = CharacterState::Attacking;
+        anim.timer = Timer::from_seconds(
+            ENEMY_WINDUP_DURATION / ENEMY_ATTACK_HIT_FRAME as f32,
+            TimerMode::Repeating,
+        );
+        commands.entity(enemy_entity).insert(EnemyWindup {
+            timer: Timer::from_seconds(ENEMY_WINDUP_DURATION, TimerMode::Once),
+            direction,
+        });
+
+        commands.spawn((
+            AttackTelegraph {
+                owner: enemy_entity,
+                full_size: Vec2::splat(ENEMY_HITBOX_RADIUS * 2.0),
+            },
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Circle::new(ENEMY_HITBOX_RADIUS)).into(),
+                material: materials.add(Color::srgba(1.0, 0.15, 0.1, 0.0)),
+                transform: Transform::from_translation(
+                    (enemy_pos + direction * ENEMY_HITBOX_OFFSET).extend(1.5),
+                )
+                .with_scale(Vec3::splat(0.2)),
+                ..default()
+            },
+        ));
+    }
+}
+
+pub fn update_attack_telegraphs(
+    mut commands: Commands,
+    windups: Query<&EnemyWindup>,
+    charges: Query<&BossCharge>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut telegraphs: Query<(
+        Entity,
+        &AttackTelegraph,
+        &mut Transform,
+        Option<&mut Sprite>,
+        Option<&Handle<ColorMaterial>>,
+    )>,
+) {
+    for (entity, telegraph, mut transform, sprite, material) in telegraphs.iter_mut() {
+        let fraction = if let Ok(windup) = windups.get(telegraph.owner) {
+            Some(windup.timer.fraction())
+        } else if let Ok(charge) = charges.get(telegraph.owner) {
+            (!charge.started).then(|| charge.windup.fraction())
+        } else {
+            None
+        };
+        let Some(t) = fraction else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let (scale, alpha) = (0.2 + 0.8 * t, 0.15 + 0.35 * t);
+        // Lane telegraphs are sprites; circular ones are meshes grown through their scale.
+        if let Some(mut sprite) = sprite {
+            sprite.custom_size = Some(telegraph.full_size * scale);
+            sprite.color = sprite.color.with_alpha(alpha);
+        } else {
+            transform.scale = Vec3::splat(scale);
+        }
+        if let Some(material) = material.and_then(|handle| materials.get_mut(handle)) {
+            material.color = material.color.with_alpha(alpha);
+        }
+    }
+}
+
+pub fn update_enemy_hitboxes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut hitboxes: Query<(Entity, &Transform, &mut EnemyHitbox)>,
+    player_query: Query<(Entity, &Transform, Option<&Invulnerable>), With<Player>>,
+    mut pets: Query<(Entity, &Transform, &mut Health), (With<Pet>, Without<PetDowned>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
+) {
+    let player = player_query.get_single().ok();
+    let mut rng = rand::thread_rng();
+    for (entity, transform, mut hitbox) in hitboxes.iter_mut() {
+        hitbox.lifetime.tick(time.delta());
+        // Overlap is tested before expiry so the final frame of a swing can still connect.
+        if let Some((player_entity, player_transform, None)) = player
+            && !hitbox.hit_entities.contains(&player_entity)
+            && in_range(
+                transform.translation.truncate(),
+                player_transform.translation.truncate(),
+                hitbox.radius + PLAYER_RADIUS,
+            )
+        {
+            hitbox.hit_entities.insert(player_entity);
             damage_events.send(DamageEvent {
                 target: player_entity,
-                attacker: Some(enemy_entity),
-                amount: enemy.damage,
-                is_crit: false,
+                attacker: Some(hitbox.owner),
+                amount: hitbox.damage,
+                damage_type: DamageType::Physical,
+                source: DamageSource::Hit,
+                kind: AttackKind::Enemy,
+                crit_bonus: 0.0,
             });
+            let chance = match hitbox.effect {
+                Some(PassiveEffect::ChanceFire(c))
+                | Some(PassiveEffect::ChanceIce(c))
+                | Some(PassiveEffect::ChanceLightning(c)) => c,
+                _ => 0.0,
+            };
+            if rng.r#gen::<f32>() < chance {
+                status_events.send(ApplyStatusEvent {
+                    target: player_entity,
+                    effect: hitbox.effect.unwrap(),
+                    applier: Some(hitbox.owner),
+                });
+            }
+        }
+        // Pets have no defenses of their own, so hits come straight off their life.
+        let center = transform.translation.truncate();
+        for (pet_entity, pet_transform, mut health) in pets.iter_mut() {
+            if !hitbox.hit_entities.contains(&pet_entity)
+                && in_range(
+                    pet_transform.translation.truncate(),
+                    center,
+                    hitbox.radius + PET_SIZE / 2.0,
+                )
+            {
+                hitbox.hit_entities.insert(pet_entity);
+                health.current -= hitbox.damage;
+            }
+        }
+        if hitbox.lifetime.finished() {
+            commands.entity(entity).despawn();
         }
     }
 }
@@ -127,9 +325,25 @@ pub fn spawn_enemies(
     let angle = rng.gen_range(0.0..std::f32::consts::TAU);
     let distance = rng.gen_range(350.0..550.0);
     let spawn_pos = player_pos + Vec2::from_angle(angle) * distance;
-    let health_scale = 1.0 + (player_level.level as f32 - 1.0) * LEVEL_HEALTH_SCALE;
-    let damage_scale = 1.0 + (player_level.level as f32 - 1.0) * LEVEL_DAMAGE_SCALE;
     let enemy_type = rng.gen_range(0..3);
+    spawn_basic_enemy(
+        &mut commands,
+        &sprites,
+        spawn_pos,
+        enemy_type,
+        player_level.level,
+    );
+}
+
+pub fn spawn_basic_enemy(
+    commands: &mut Commands,
+    sprites: &CharacterSprites,
+    spawn_pos: Vec2,
+    enemy_type: u32,
+    level: u32,
+) -> Entity {
+    let health_scale = 1.0 + (level as f32 - 1.0) * LEVEL_HEALTH_SCALE;
+    let damage_scale = 1.0 + (level as f32 - 1.0) * LEVEL_DAMAGE_SCALE;
     let (size, color, health, damage, xp, speed, enemy_size) = match enemy_type {
         0 => (
             Vec2::new(170.0, 170.0),
@@ -222,6 +436,17 @@ pub fn spawn_enemies(
             HealthBarFill(size.x + 6.0),
         ));
     });
+
+    let mut rng = rand::thread_rng();
+    if rng.r#gen::<f32>() < ENEMY_ELEMENTAL_CHANCE {
+        let effect = match rng.gen_range(0..3) {
+            0 => PassiveEffect::ChanceFire(ENEMY_ELEMENT_PROC_CHANCE),
+            1 => PassiveEffect::ChanceIce(ENEMY_ELEMENT_PROC_CHANCE),
+            _ => PassiveEffect::ChanceLightning(ENEMY_ELEMENT_PROC_CHANCE),
+        };
+        commands.entity(enemy_entity).insert(EnemyElement(effect));
+    }
+    enemy_entity
 }
 
 pub fn check_enemy_death(
@@ -297,10 +522,13 @@ pub fn check_enemy_death(
                     },
                     AoeEffect {
                         damage: 40.0,
+                        damage_type: DamageType::Fire,
+                        crit_bonus: 0.0,
                         owner: player_entity,
                         tick_timer: Timer::from_seconds(0.01, TimerMode::Once),
                         duration: Timer::from_seconds(0.1, TimerMode::Once),
                         hit_this_tick: HashSet::new(),
+                        kind: AttackKind::Explosion,
                     },
                 ));
             }
@@ -327,46 +555,90 @@ pub fn handle_status_applications(
 ) {
     for event in events.read() {
         if let Ok(mut status) = query.get_mut(event.target) {
-            match event.effect {
-                PassiveEffect::ChanceFire(_) => {
-                    status.fire_stacks = (status.fire_stacks + 1).min(10)
-                }
-                PassiveEffect::ChanceIce(_) => status.ice_stacks = (status.ice_stacks + 1).min(10),
-                PassiveEffect::ChanceLightning(_) => {
-                    status.lightning_stacks = (status.lightning_stacks + 1).min(10)
-                }
-                _ => {}
-            }
+            status.add_stack(event.effect, event.applier);
         }
     }
 }
 
 pub fn update_elemental_statuses(
+    mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(Entity, &mut ElementalStatus, &mut Sprite, &mut Enemy)>,
+    accessibility: Res<Accessibility>,
+    mut query: Query<
+        (
+            Entity,
+            &mut ElementalStatus,
+            &mut Sprite,
+            Option<&mut Frozen>,
+        ),
+        With<Enemy>,
+    >,
     mut damage_events: EventWriter<DamageEvent>,
-    player_query: Query<Entity, With<Player>>,
 ) {
-    let player_entity = player_query.get_single().ok();
-    for (entity, status, mut sprite, _enemy) in query.iter_mut() {
-        if status.fire_stacks > status.ice_stacks && status.fire_stacks > status.lightning_stacks {
-            sprite.color = Color::srgb(1.0, 0.5, 0.5);
+    for (entity, mut status, mut sprite, frozen) in query.iter_mut() {
+        // Re-captured while untinted so later color changes (e.g. enrage) aren't undone.
+        if !status.tinted {
+            status.base_color = Some(sprite.color);
+        }
+        status.tick(time.delta());
+
+        if let Some(mut frozen) = frozen {
+            frozen.0.tick(time.delta());
+            if frozen.0.finished() {
+                commands.entity(entity).remove::<Frozen>();
+                status.thaw();
+            }
+        } else if status.ice_stacks >= MAX_ELEMENTAL_STACKS && !status.is_frozen {
+            status.is_frozen = true;
+            commands
+                .entity(entity)
+                .try_insert(Frozen(Timer::from_seconds(
+                    FREEZE_DURATION,
+                    TimerMode::Once,
+                )));
+        }
+
+        if status.burn_timer.just_finished() {
+            for (applier, stacks) in status.burn_sources() {
+                damage_events.send(DamageEvent {
+                    target: entity,
+                    attacker: applier,
+                    amount: ENEMY_BURN_DAMAGE_PER_STACK * stacks as f32,
+                    damage_type: DamageType::Fire,
+                    source: DamageSource::DoT,
+                    kind: AttackKind::Burn,
+                    crit_bonus: 0.0,
+                });
+            }
+        }
+
+        let palette = accessibility.palette;
+        let tint = if status.is_frozen {
+            Some(palette.frozen_tint())
+        } else if status.fire_stacks > status.ice_stacks
+            && status.fire_stacks > status.lightning_stacks
+        {
+            Some(palette.element_tint(DamageType::Fire))
         } else if status.ice_stacks > status.fire_stacks
             && status.ice_stacks > status.lightning_stacks
         {
-            sprite.color = Color::srgb(0.5, 0.8, 1.0);
+            Some(palette.element_tint(DamageType::Cold))
         } else if status.lightning_stacks > status.fire_stacks
             && status.lightning_stacks > status.ice_stacks
         {
-            sprite.color = Color::srgb(1.0, 1.0, 0.5);
-        }
-        if status.fire_stacks >= 10 {
-            damage_events.send(DamageEvent {
-                target: entity,
-                attacker: player_entity,
-                amount: 5.0 * time.delta_seconds() * 60.0,
-                is_crit: false,
-            });
+            Some(palette.element_tint(DamageType::Lightning))
+        } else {
+            None
+        };
+
+        if let Some(color) = tint {
+            sprite.color = color;
+            status.tinted = true;
+        } else if status.tinted {
+            if let Some(base) = status.base_color {
+                sprite.color = base;
+            }
+            status.tinted = false;
         }
     }
 }
@@ -375,12 +647,16 @@ pub fn handle_mastery_effects(
     mut commands: Commands,
     player_query: Query<(Entity, &PlayerPassives), With<Player>>,
     mut enemies: Query<(Entity, &mut ElementalStatus, &Transform), With<Enemy>>,
+    newly_frozen: Query<&Transform, (With<Enemy>, Added<Frozen>)>,
 ) {
     let Ok((player_entity, passives)) = player_query.get_single() else {
         return;
     };
     for (_entity, mut status, transform) in enemies.iter_mut() {
-        if status.fire_stacks >= 10 && passives.unlocked_nodes.contains(&12) && !status.is_ignited {
+        if status.fire_stacks >= MAX_ELEMENTAL_STACKS
+            && passives.unlocked_nodes.contains(&12)
+            && !status.is_ignited
+        {
             status.is_ignited = true;
             commands.spawn((
                 SpriteBundle {
@@ -394,33 +670,172 @@ pub fn handle_mastery_effects(
                 },
                 AoeEffect {
                     damage: 60.0,
+                    damage_type: DamageType::Fire,
+                    crit_bonus: 0.0,
                     owner: player_entity,
                     tick_timer: Timer::from_seconds(0.01, TimerMode::Once),
                     duration: Timer::from_seconds(0.15, TimerMode::Once),
                     hit_this_tick: HashSet::new(),
+                    kind: AttackKind::Explosion,
                 },
             ));
         }
-        if status.ice_stacks >= 10 && passives.unlocked_nodes.contains(&15) && !status.is_frozen {
-            status.is_frozen = true;
-            commands.spawn((
-                SpriteBundle {
-                    sprite: Sprite {
-                        color: Color::srgba(0.0, 0.8, 1.0, 0.8),
-                        custom_size: Some(Vec2::splat(150.0)),
-                        ..default()
-                    },
-                    transform: Transform::from_translation(transform.translation),
+    }
+    if !passives.unlocked_nodes.contains(&15) {
+        return;
+    }
+    for transform in newly_frozen.iter() {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgba(0.0, 0.8, 1.0, 0.8),
+                    custom_size: Some(Vec2::splat(150.0)),
                     ..default()
                 },
-                AoeEffect {
-                    damage: 80.0,
-                    owner: player_entity,
-                    tick_timer: Timer::from_seconds(0.01, TimerMode::Once),
-                    duration: Timer::from_seconds(0.15, TimerMode::Once),
-                    hit_this_tick: HashSet::new(),
-                },
-            ));
+                transform: Transform::from_translation(transform.translation),
+                ..default()
+            },
+            AoeEffect {
+                damage: 80.0,
+                damage_type: DamageType::Cold,
+                crit_bonus: 0.0,
+                owner: player_entity,
+                tick_timer: Timer::from_seconds(0.01, TimerMode::Once),
+                duration: Timer::from_seconds(0.15, TimerMode::Once),
+                hit_this_tick: HashSet::new(),
+                kind: AttackKind::Explosion,
+            },
+        ));
+    }
+}
+
+pub fn chain_lightning_discharge(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: Query<(Entity, &PlayerPassives), With<Player>>,
+    mut enemies: Query<
+        (
+            Entity,
+            &mut ElementalStatus,
+            &Transform,
+            Option<&mut DischargeCooldown>,
+        ),
+        With<Enemy>,
+    >,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let Ok((player_entity, passives)) = player_query.get_single() else {
+        return;
+    };
+    let has_mastery = passives.unlocked_nodes.contains(&18);
+
+    let mut sources = Vec::new();
+    for (entity, mut status, transform, cooldown) in enemies.iter_mut() {
+        if let Some(mut cooldown) = cooldown {
+            cooldown.0.tick(time.delta());
+            if cooldown.0.finished() {
+                commands.entity(entity).remove::<DischargeCooldown>();
+            }
+            continue;
+        }
+        if has_mastery && status.lightning_stacks >= MAX_ELEMENTAL_STACKS && !status.is_discharged {
+            status.discharge();
+            sources.push((entity, transform.translation.truncate()));
+        }
+    }
+    if sources.is_empty() {
+        return;
+    }
+
+    let shocked: Vec<(Entity, Vec2)> = enemies
+        .iter()
+        .filter(|(_, status, _, _)| status.lightning_stacks > 0)
+        .map(|(entity, _, transform, _)| (entity, transform.translation.truncate()))
+        .collect();
+
+    for (source, source_pos) in sources {
+        commands
+            .entity(source)
+            .try_insert(DischargeCooldown(Timer::from_seconds(
+                CHAIN_LIGHTNING_COOLDOWN,
+                TimerMode::Once,
+            )));
+        spawn_lightning_bolt(&mut commands, source_pos + Vec2::Y * 120.0, source_pos);
+        damage_events.send(DamageEvent {
+            target: source,
+            attacker: Some(player_entity),
+            amount: CHAIN_LIGHTNING_DAMAGE,
+            damage_type: DamageType::Lightning,
+            source: DamageSource::Explosion,
+            kind: AttackKind::Explosion,
+            crit_bonus: 0.0,
+        });
+
+        let mut hit = HashSet::from([source]);
+        let mut current = source_pos;
+        let mut damage = CHAIN_LIGHTNING_DAMAGE;
+        for _ in 0..CHAIN_LIGHTNING_MAX_JUMPS {
+            let next = shocked
+                .iter()
+                .filter(|(e, pos)| {
+                    !hit.contains(e) && in_range(*pos, current, CHAIN_LIGHTNING_RANGE)
+                })
+                .min_by(|a, b| {
+                    a.1.distance_squared(current)
+                        .total_cmp(&b.1.distance_squared(current))
+                });
+            let Some(&(next_entity, next_pos)) = next else {
+                break;
+            };
+
+            damage *= CHAIN_LIGHTNING_FALLOFF;
+            spawn_lightning_bolt(&mut commands, current, next_pos);
+            damage_events.send(DamageEvent {
+                target: next_entity,
+                attacker: Some(player_entity),
+                amount: damage,
+                damage_type: DamageType::Lightning,
+                source: DamageSource::Explosion,
+                kind: AttackKind::Explosion,
+                crit_bonus: 0.0,
+            });
+            hit.insert(next_entity);
+            current = next_pos;
+        }
+    }
+}
+
+fn spawn_lightning_bolt(commands: &mut Commands, from: Vec2, to: Vec2) {
+    let delta = to - from;
+    commands.spawn((
+        LightningBolt(Timer::from_seconds(
+            LIGHTNING_BOLT_DURATION,
+            TimerMode::Once,
+        )),
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(0.7, 0.9, 1.0, 1.0),
+                custom_size: Some(Vec2::new(delta.length(), 5.0)),
+                ..default()
+            },
+            transform: Transform::from_translation(((from + to) / 2.0).extend(15.0))
+                .with_rotation(Quat::from_rotation_z(delta.y.atan2(delta.x))),
+            ..default()
+        },
+    ));
+}
+
+pub fn update_lightning_bolts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut bolts: Query<(Entity, &mut LightningBolt, &mut Sprite)>,
+) {
+    for (entity, mut bolt, mut sprite) in bolts.iter_mut() {
+        bolt.0.tick(time.delta());
+        if bolt.0.finished() {
+            commands.entity(entity).despawn();
+        } else {
+            sprite.color.set_alpha(1.0 - bolt.0.fraction());
         }
     }
 }
@@ -0,0 +1,194 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::resources::*;
+
+/// Queues a floating text from code that only has `Commands`, such as spawn helpers.
+pub fn queue_floating_text(commands: &mut Commands, event: FloatingTextEvent) {
+    commands.add(move |world: &mut World| {
+        world.send_event(event);
+    });
+}
+
+/// Folds same-frame numbers on the same target into one request before touching the pool.
+fn merge_requests<'a>(
+    events: impl Iterator<Item = &'a FloatingTextEvent>,
+    settings: &Settings,
+) -> Vec<FloatingTextEvent> {
+    let mut requests: Vec<FloatingTextEvent> = Vec::new();
+    for event in events {
+        if event.style.is_number() && !settings.damage_numbers {
+            continue;
+        }
+        if let FloatingTextContent::Amount(amount) = event.content
+            && event.target.is_some()
+            && let Some(group) = event.style.merge_group()
+            && let Some(existing) = requests.iter_mut().find(|request| {
+                request.target == event.target && request.style.merge_group() == Some(group)
+            })
+            && let FloatingTextContent::Amount(total) = &mut existing.content
+        {
+            *total += amount;
+            if event.style.priority() > existing.style.priority() {
+                existing.style = event.style;
+                existing.color = event.color;
+            }
+            continue;
+        }
+        requests.push(event.clone());
+    }
+    requests
+}
+
+pub fn show_floating_texts(
+    mut commands: Commands,
+    mut events: EventReader<FloatingTextEvent>,
+    settings: Res<Settings>,
+    mut texts: Query<(
+        Entity,
+        &mut FloatingText,
+        &mut Text,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+) {
+    let mut rng = rand::thread_rng();
+    let mut pool_size = texts.iter().count();
+
+    for request in merge_requests(events.read(), &settings) {
+        let FloatingTextEvent {
+            content,
+            style,
+            color,
+            position,
+            target,
+        } = request;
+        // Merge into a number that is still on screen for this target.
+        if let FloatingTextContent::Amount(amount) = content
+            && target.is_some()
+            && let Some((_, mut floating, mut text, ..)) = texts.iter_mut().find(|(_, f, ..)| {
+                f.active
+                    && f.target == target
+                    && f.style.merge_group().is_some()
+                    && f.style.merge_group() == style.merge_group()
+                    && f.lifetime.elapsed_secs() < FLOATING_TEXT_MERGE_WINDOW
+            })
+        {
+            floating.amount += amount;
+            if style.priority() > floating.style.priority() {
+                floating.style = style;
+                text.sections[0].style.font_size = style.font_size();
+                text.sections[0].style.color = color;
+            }
+            floating.lifetime.reset();
+            text.sections[0].value = floating.style.format_amount(floating.amount);
+            continue;
+        }
+
+        let (label, amount) = match content {
+            FloatingTextContent::Amount(amount) => (style.format_amount(amount), amount),
+            FloatingTextContent::Label(label) => (label, 0.0),
+        };
+        let (offset, velocity) = if style.merge_group().is_some() {
+            (
+                Vec2::new(rng.gen_range(-15.0..15.0), 20.0),
+                style.velocity() + Vec2::new(rng.gen_range(-25.0..25.0), 0.0),
+            )
+        } else {
+            (Vec2::ZERO, style.velocity())
+        };
+        let floating = FloatingText {
+            style,
+            target,
+            amount,
+            velocity,
+            lifetime: Timer::from_seconds(style.lifetime(), TimerMode::Once),
+            active: true,
+        };
+        let text_style = TextStyle {
+            font_size: style.font_size(),
+            color,
+            ..default()
+        };
+        let translation = (position.truncate() + offset).extend(100.0);
+
+        // Reuse a hidden entry, then grow the pool up to the cap, then evict the
+        // lowest-priority entry closest to expiring if it doesn't outrank this one.
+        let slot = texts
+            .iter()
+            .find(|(_, f, ..)| !f.active)
+            .map(|(entity, ..)| entity)
+            .or_else(|| {
+                if pool_size < FLOATING_TEXT_CAP {
+                    return None;
+                }
+                texts
+                    .iter()
+                    .filter(|(_, f, ..)| f.style.priority() <= style.priority())
+                    .min_by(|(_, a, ..), (_, b, ..)| {
+                        a.style
+                            .priority()
+                            .cmp(&b.style.priority())
+                            .then(a.lifetime.remaining().cmp(&b.lifetime.remaining()))
+                    })
+                    .map(|(entity, ..)| entity)
+            });
+
+        match slot {
+            Some(entity) => {
+                let Ok((_, mut slot, mut text, mut transform, mut visibility)) =
+                    texts.get_mut(entity)
+                else {
+                    continue;
+                };
+                *slot = floating;
+                *text = Text::from_section(label, text_style);
+                transform.translation = translation;
+                *visibility = Visibility::Visible;
+            }
+            None if pool_size < FLOATING_TEXT_CAP => {
+                pool_size += 1;
+                commands.spawn((
+                    Text2dBundle {
+                        text: Text::from_section(label, text_style),
+                        transform: Transform::from_translation(translation),
+                        ..default()
+                    },
+                    floating,
+                ));
+            }
+            None => {}
+        }
+    }
+}
+
+pub fn update_floating_texts(
+    time: Res<Time>,
+    mut texts: Query<(
+        &mut FloatingText,
+        &mut Transform,
+        &mut Text,
+        &mut Visibility,
+    )>,
+) {
+    for (mut floating, mut transform, mut text, mut visibility) in texts.iter_mut() {
+        if !floating.active {
+            continue;
+        }
+        floating.lifetime.tick(time.delta());
+        if floating.lifetime.finished() {
+            floating.active = false;
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        floating.velocity.y -= FLOATING_TEXT_GRAVITY * time.delta_seconds();
+        transform.translation += (floating.velocity * time.delta_seconds()).extend(0.0);
+        let alpha = 1.0 - floating.lifetime.fraction();
+        for section in text.sections.iter_mut() {
+            section.style.color = section.style.color.with_alpha(alpha);
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use bevy::prelude::*;
+
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::*;
+
+const CARD_COLOR: Color = Color::srgb(0.15, 0.15, 0.2);
+const CARD_HOVER_COLOR: Color = Color::srgb(0.22, 0.22, 0.3);
+const BUTTON_COLOR: Color = Color::srgb(0.2, 0.2, 0.25);
+const BUTTON_HOVER_COLOR: Color = Color::srgb(0.25, 0.25, 0.3);
+
+pub fn setup_level_up_ui(
+    mut commands: Commands,
+    mut level_up: ResMut<LevelUpOffers>,
+    player_query: Query<&Player>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    if level_up.offers.is_empty() {
+        level_up.roll(player.class, &mut rand::thread_rng());
+    }
+
+    commands
+        .spawn((
+            LevelUpUi,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.7).into(),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "LEVEL UP",
+                TextStyle {
+                    font_size: 48.0,
+                    color: Color::srgb(1.0, 0.9, 0.2),
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        margin: UiRect::top(Val::Px(30.0)),
+                        column_gap: Val::Px(20.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    for index in 0..LEVEL_UP_OFFER_COUNT {
+                        row.spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                row_gap: Val::Px(8.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|column| {
+                            column
+                                .spawn((
+                                    UpgradeCard(index),
+                                    ButtonBundle {
+                                        style: Style {
+                                            width: Val::Px(200.0),
+                                            height: Val::Px(140.0),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
+                                            padding: UiRect::all(Val::Px(10.0)),
+                                            ..default()
+                                        },
+                                        background_color: CARD_COLOR.into(),
+                                        border_color: Color::srgb(0.8, 0.7, 0.3).into(),
+                                        ..default()
+                                    },
+                                ))
+                                .with_children(|card| {
+                                    card.spawn((
+                                        TextBundle::from_sections([
+                                            TextSection::new(
+                                                "",
+                                                TextStyle {
+                                                    font_size: 14.0,
+                                                    color: Color::srgb(0.5, 0.5, 0.55),
+                                                    ..default()
+                                                },
+                                            ),
+                                            TextSection::new(
+                                                "",
+                                                TextStyle {
+                                                    font_size: 24.0,
+                                                    color: Color::WHITE,
+                                                    ..default()
+                                                },
+                                            ),
+                                            TextSection::new(
+                                                "",
+                                                TextStyle {
+                                                    font_size: 16.0,
+                                                    color: Color::srgb(0.5, 0.9, 0.5),
+                                                    ..default()
+                                                },
+                                            ),
+                                        ])
+                                        .with_text_justify(JustifyText::Center),
+                                        UpgradeCardText(index),
+                                    ));
+                                });
+
+                            column
+                                .spawn((
+                                    BanishButton(index),
+                                    ButtonBundle {
+                                        style: Style {
+                                            padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                                            ..default()
+                                        },
+                                        background_color: BUTTON_COLOR.into(),
+                                        ..default()
+                                    },
+                                ))
+                                .with_children(|btn| {
+                                    btn.spawn(TextBundle::from_section(
+                                        "Banish",
+                                        TextStyle {
+                                            font_size: 14.0,
+                                            color: Color::srgb(0.9, 0.5, 0.5),
+                                            ..default()
+                                        },
+                                    ));
+                                });
+                        });
+                    }
+                });
+
+            parent
+                .spawn((
+                    RerollButton,
+                    ButtonBundle {
+                        style: Style {
+                            margin: UiRect::top(Val::Px(25.0)),
+                            padding: UiRect::axes(Val::Px(20.0), Val::Px(8.0)),
+                            ..default()
+                        },
+                        background_color: BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                ))
+                .with_children(|btn| {
+                    btn.spawn(TextBundle::from_section(
+                        "Reroll (R)",
+                        TextStyle {
+                            font_size: 18.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    ));
+                });
+
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 16.0,
+                        color: Color::srgb(0.7, 0.7, 0.7),
+                        ..default()
+                    },
+                ),
+                LevelUpStatusText,
+            ));
+        });
+}
+
+pub fn despawn_level_up_ui(mut commands: Commands, query: Query<Entity, With<LevelUpUi>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn handle_level_up_choice(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut level_up: ResMut<LevelUpOffers>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut player_query: Query<(&Player, &mut BaseStats, &mut Health)>,
+    mut cards: Query<
+        (&Interaction, &UpgradeCard, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    let Ok((player, mut base_stats, mut health)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    let mut picked = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3]
+        .iter()
+        .position(|key| keyboard.just_pressed(*key));
+    for (interaction, card, mut bg_color) in cards.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => picked = Some(card.0),
+            Interaction::Hovered => *bg_color = CARD_HOVER_COLOR.into(),
+            Interaction::None => *bg_color = CARD_COLOR.into(),
+        }
+    }
+    let Some(upgrade) = picked.and_then(|index| level_up.offers.get(index).copied()) else {
+        // Nothing left to offer: let the level go rather than trapping the player here.
+        if level_up.offers.is_empty() {
+            level_up.pending = 0;
+            next_state.set(GameState::Playing);
+        }
+        return;
+    };
+
+    upgrade.apply(&mut base_stats.0, &mut health);
+    level_up.pending = level_up.pending.saturating_sub(1);
+    if level_up.pending > 0 {
+        level_up.roll(player.class, &mut rand::thread_rng());
+    } else {
+        level_up.offers.clear();
+        next_state.set(GameState::Playing);
+    }
+}
+
+pub fn handle_level_up_reroll(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut level_up: ResMut<LevelUpOffers>,
+    player_query: Query<&Player>,
+    mut reroll_buttons: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<RerollButton>),
+    >,
+    mut banish_buttons: Query<
+        (&Interaction, &BanishButton, &mut BackgroundColor),
+        (Changed<Interaction>, Without<RerollButton>),
+    >,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    let mut rng = rand::thread_rng();
+
+    let mut reroll = keyboard.just_pressed(KeyCode::KeyR);
+    for (interaction, mut bg_color) in reroll_buttons.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => reroll = true,
+            Interaction::Hovered => *bg_color = BUTTON_HOVER_COLOR.into(),
+            Interaction::None => *bg_color = BUTTON_COLOR.into(),
+        }
+    }
+    if reroll && level_up.rerolls > 0 {
+        level_up.rerolls -= 1;
+        level_up.roll(player.class, &mut rng);
+    }
+
+    for (interaction, banish, mut bg_color) in banish_buttons.iter_mut() {
+        match *interaction {
+            Interaction::Pressed if level_up.banishes > 0 => {
+                level_up.banish(banish.0, player.class, &mut rng);
+            }
+            Interaction::Hovered => *bg_color = BUTTON_HOVER_COLOR.into(),
+            _ => *bg_color = BUTTON_COLOR.into(),
+        }
+    }
+}
+
+pub fn update_level_up_ui(
+    level_up: Res<LevelUpOffers>,
+    mut card_texts: Query<(&mut Text, &UpgradeCardText), Without<LevelUpStatusText>>,
+    mut cards: Query<(&mut Visibility, &UpgradeCard)>,
+    mut status_text: Query<&mut Text, With<LevelUpStatusText>>,
+) {
+    for (mut text, card) in card_texts.iter_mut() {
+        let Some(upgrade) = level_up.offers.get(card.0) else {
+            continue;
+        };
+        text.sections[0].value = format!("[{}]\n", card.0 + 1);
+        text.sections[1].value = format!("{}\n", upgrade.name());
+        text.sections[2].value = upgrade.description().to_string();
+    }
+    for (mut visibility, card) in cards.iter_mut() {
+        *visibility = if card.0 < level_up.offers.len() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if let Ok(mut text) = status_text.get_single_mut() {
+        text.sections[0].value = format!(
+            "Rerolls: {}   Banishes: {}   Levels to spend: {}",
+            level_up.rerolls, level_up.banishes, level_up.pending
+        );
+    }
+}
@@ -1,10 +1,18 @@
+pub mod affixes;
 pub mod animation;
+pub mod audio;
+pub mod boss;
 pub mod combat;
 pub mod enemy;
+pub mod floating_text;
+pub mod level_up;
 pub mod passive_ui;
+pub mod pause;
 pub mod pets;
 pub mod player;
 pub mod progression;
+pub mod projectiles;
 pub mod selection;
+pub mod skills;
 pub mod ui;
 pub mod world;
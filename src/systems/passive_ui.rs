@@ -1,7 +1,9 @@
 use crate::components::*;
+use crate::helpers::*;
 use crate::resources::*;
+use bevy::input::keyboard::KeyboardInput;
 use bevy::prelude::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 #[derive(Component)]
 pub struct PassiveNodeButton(pub u32);
@@ -24,6 +26,33 @@ pub struct PassiveTreeContainer;
 #[derive(Component)]
 pub struct CenterViewButton;
 
+#[derive(Component)]
+pub struct RespecButton;
+
+#[derive(Component)]
+pub struct RespecButtonText;
+
+#[derive(Component)]
+pub struct PlanningButton;
+
+#[derive(Component)]
+pub struct PlanningButtonText;
+
+#[derive(Component)]
+pub struct SearchBox;
+
+#[derive(Component)]
+pub struct SearchText;
+
+#[derive(Component)]
+pub struct LegendFilterButton(pub NodeCategory);
+
+#[derive(Component)]
+pub struct ExportBuildButton;
+
+#[derive(Component)]
+pub struct BuildCodeText;
+
 #[derive(Component)]
 pub struct PathHighlight;
 
@@ -34,6 +63,10 @@ pub struct PassiveTreeViewState {
     pub dragging: bool,
     pub last_mouse_pos: Vec2,
     pub hovered_node_id: Option<u32>,
+    pub planning: bool,
+    pub search: String,
+    pub search_focused: bool,
+    pub hidden_categories: Vec<NodeCategory>,
 }
 
 impl Default for PassiveTreeViewState {
@@ -44,6 +77,10 @@ impl Default for PassiveTreeViewState {
             dragging: false,
             last_mouse_pos: Vec2::ZERO,
             hovered_node_id: None,
+            planning: false,
+            search: String::new(),
+            search_focused: false,
+            hidden_categories: Vec::new(),
         }
     }
 }
@@ -58,6 +95,16 @@ pub enum NodeCategory {
 }
 
 impl NodeCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeCategory::Damage => "Damage",
+            NodeCategory::Defense => "Defense",
+            NodeCategory::Utility => "Utility",
+            NodeCategory::Elemental => "Elemental",
+            NodeCategory::Origin => "Origin",
+        }
+    }
+
     pub fn from_effect(effect: &PassiveEffect) -> Self {
         match effect {
             PassiveEffect::StatAdd(stats) => {
@@ -67,7 +114,14 @@ impl NodeCategory {
                     || stats.attack_speed > 0.0
                 {
                     NodeCategory::Damage
-                } else if stats.armor > 0.0 || stats.life_regen > 0.0 {
+                } else if stats.armor > 0.0
+                    || stats.life_regen > 0.0
+                    || stats.fire_resistance > 0.0
+                    || stats.cold_resistance > 0.0
+                    || stats.lightning_resistance > 0.0
+                    || stats.evasion > 0.0
+                    || stats.block_chance > 0.0
+                {
                     NodeCategory::Defense
                 } else if stats.speed > 0.0 {
                     NodeCategory::Utility
@@ -88,9 +142,15 @@ impl NodeCategory {
                     NodeCategory::Utility
                 }
             }
-            PassiveEffect::Ricochet | PassiveEffect::Explosion | PassiveEffect::Knockback => {
-                NodeCategory::Utility
-            }
+            PassiveEffect::Ricochet
+            | PassiveEffect::Explosion
+            | PassiveEffect::Knockback
+            | PassiveEffect::Homing
+            | PassiveEffect::SplitProjectiles
+            | PassiveEffect::ForkProjectiles
+            | PassiveEffect::ReturningProjectiles
+            | PassiveEffect::OrbitingProjectiles => NodeCategory::Utility,
+            PassiveEffect::LuckyCrit => NodeCategory::Damage,
             PassiveEffect::ChanceFire(_)
             | PassiveEffect::ChanceIce(_)
             | PassiveEffect::ChanceLightning(_)
@@ -99,11 +159,46 @@ impl NodeCategory {
             | PassiveEffect::MasteryLightning => NodeCategory::Elemental,
             PassiveEffect::ShieldRegen(_)
             | PassiveEffect::ShieldLeech(_)
-            | PassiveEffect::LifeLeech(_) => NodeCategory::Defense,
+            | PassiveEffect::LifeLeech(_)
+            | PassiveEffect::BlockChance(_)
+            | PassiveEffect::Taunt(_) => NodeCategory::Defense,
+            PassiveEffect::ProjectileCount(_) | PassiveEffect::PetDamage(_) => NodeCategory::Damage,
+            PassiveEffect::ProjectilePierce(_)
+            | PassiveEffect::SkillCooldown(_)
+            | PassiveEffect::AoeSize(_)
+            | PassiveEffect::PetLife(_)
+            | PassiveEffect::PetCount(_)
+            | PassiveEffect::SkillLifeCost(_) => NodeCategory::Utility,
+            PassiveEffect::CannotEvade => NodeCategory::Defense,
+            PassiveEffect::MoreDamage(_) => NodeCategory::Damage,
+            PassiveEffect::LessMaxLife(_) => NodeCategory::Defense,
+            PassiveEffect::Compound(effects) => effects
+                .first()
+                .map_or(NodeCategory::Utility, NodeCategory::from_effect),
         }
     }
 
-    pub fn base_color(&self) -> Color {
+    /// Colorblind-safe replacement for the standard category color.
+    fn palette_color(&self, palette: ColorPalette) -> Option<Color> {
+        let color = match (palette, self) {
+            (ColorPalette::Standard, _) => return None,
+            (_, NodeCategory::Origin) => Color::srgb(0.95, 0.95, 0.95),
+            (ColorPalette::RedGreen, NodeCategory::Damage) => Color::srgb(0.9, 0.6, 0.0),
+            (ColorPalette::RedGreen, NodeCategory::Defense) => Color::srgb(0.0, 0.45, 0.7),
+            (ColorPalette::RedGreen, NodeCategory::Utility) => Color::srgb(0.95, 0.9, 0.25),
+            (ColorPalette::RedGreen, NodeCategory::Elemental) => Color::srgb(0.8, 0.6, 0.7),
+            (ColorPalette::BlueYellow, NodeCategory::Damage) => Color::srgb(0.9, 0.2, 0.25),
+            (ColorPalette::BlueYellow, NodeCategory::Defense) => Color::srgb(0.0, 0.6, 0.6),
+            (ColorPalette::BlueYellow, NodeCategory::Utility) => Color::srgb(0.6, 0.6, 0.6),
+            (ColorPalette::BlueYellow, NodeCategory::Elemental) => Color::srgb(1.0, 0.55, 0.85),
+        };
+        Some(color)
+    }
+
+    pub fn base_color(&self, palette: ColorPalette) -> Color {
+        if let Some(color) = self.palette_color(palette) {
+            return color;
+        }
         match self {
             NodeCategory::Origin => Color::srgb(1.0, 0.9, 0.4),
             NodeCategory::Damage => Color::srgb(0.9, 0.3, 0.2),
@@ -113,7 +208,10 @@ impl NodeCategory {
         }
     }
 
-    pub fn glow_color(&self) -> Color {
+    pub fn glow_color(&self, palette: ColorPalette) -> Color {
+        if let Some(color) = self.palette_color(palette) {
+            return color.mix(&Color::WHITE, 0.3);
+        }
         match self {
             NodeCategory::Origin => Color::srgb(1.0, 0.95, 0.6),
             NodeCategory::Damage => Color::srgb(1.0, 0.5, 0.3),
@@ -123,7 +221,10 @@ impl NodeCategory {
         }
     }
 
-    pub fn locked_color(&self) -> Color {
+    pub fn locked_color(&self, palette: ColorPalette) -> Color {
+        if let Some(color) = self.palette_color(palette) {
+            return color.mix(&Color::BLACK, 0.65);
+        }
         match self {
             NodeCategory::Origin => Color::srgb(0.4, 0.35, 0.15),
             NodeCategory::Damage => Color::srgb(0.35, 0.15, 0.1),
@@ -142,32 +243,78 @@ pub fn get_node_category(node_id: u32, effect: &PassiveEffect) -> NodeCategory {
     }
 }
 
-fn find_path_to_node(target_id: u32, tree: &PassiveTree, unlocked: &[u32]) -> Vec<u32> {
+/// Shortest allocation path from the allocated tree (or the class root) to `target_id`,
+/// starting with the node it branches off from.
+fn find_path_to_node(
+    target_id: u32,
+    tree: &PassiveTree,
+    unlocked: &[u32],
+    class: PlayerClass,
+) -> Vec<u32> {
     if unlocked.contains(&target_id) {
         return vec![target_id];
     }
 
-    let mut path = vec![target_id];
-    let mut current = target_id;
+    let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+    for &(a, b) in &tree.connections {
+        adjacency.entry(a).or_default().push(b);
+        adjacency.entry(b).or_default().push(a);
+    }
 
-    while current != 0 {
-        if let Some(node) = tree.nodes.get(&current) {
-            if let Some(&req) = node.requirements.first() {
-                path.push(req);
-                current = req;
-                if unlocked.contains(&req) {
-                    break;
-                }
-            } else {
-                break;
+    let mut queue: VecDeque<u32> = if unlocked.is_empty() {
+        tree.class_root(class).into_iter().collect()
+    } else {
+        unlocked.iter().copied().collect()
+    };
+    let mut visited: HashSet<u32> = queue.iter().copied().collect();
+    let mut came_from: HashMap<u32, u32> = HashMap::new();
+
+    while let Some(current) = queue.pop_front() {
+        if current == target_id {
+            let mut path = vec![current];
+            let mut step = current;
+            while let Some(&prev) = came_from.get(&step) {
+                path.push(prev);
+                step = prev;
+            }
+            path.reverse();
+            return path;
+        }
+        for &next in adjacency.get(&current).into_iter().flatten() {
+            // Only walk outward: `next` has to be unlockable from `current`.
+            let outward = tree.nodes.get(&next).is_some_and(|node| {
+                node.requirements.contains(&current) && node.class.is_none_or(|c| c == class)
+            });
+            if outward && visited.insert(next) {
+                came_from.insert(next, current);
+                queue.push_back(next);
             }
-        } else {
-            break;
         }
     }
+    Vec::new()
+}
+
+fn node_matches_search(node: &PassiveNode, category: NodeCategory, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    node.name.to_lowercase().contains(&query)
+        || node.description.to_lowercase().contains(&query)
+        || node
+            .drawback
+            .as_ref()
+            .is_some_and(|drawback| drawback.to_lowercase().contains(&query))
+        || category.name().to_lowercase().contains(&query)
+}
 
-    path.reverse();
-    path
+fn nodes_to_allocate(
+    target_id: u32,
+    tree: &PassiveTree,
+    unlocked: &[u32],
+    class: PlayerClass,
+) -> Vec<u32> {
+    find_path_to_node(target_id, tree, unlocked, class)
+        .into_iter()
+        .filter(|id| !unlocked.contains(id))
+        .collect()
 }
 
 fn format_stat_preview(current_stats: &Stats, node: &PassiveNode) -> String {
@@ -223,6 +370,35 @@ fn format_stat_preview(current_stats: &Stats, node: &PassiveNode) -> String {
                     current_stats.armor + add.armor
                 ));
             }
+            for (label, current, added) in [
+                (
+                    "Fire Res",
+                    current_stats.fire_resistance,
+                    add.fire_resistance,
+                ),
+                (
+                    "Cold Res",
+                    current_stats.cold_resistance,
+                    add.cold_resistance,
+                ),
+                (
+                    "Lightning Res",
+                    current_stats.lightning_resistance,
+                    add.lightning_resistance,
+                ),
+                ("Evasion", current_stats.evasion, add.evasion),
+                ("Block", current_stats.block_chance, add.block_chance),
+                ("Block Amount", current_stats.block_amount, add.block_amount),
+            ] {
+                if added > 0.0 {
+                    parts.push(format!(
+                        "{}: {:.0}% -> {:.0}%",
+                        label,
+                        current * 100.0,
+                        (current + added) * 100.0
+                    ));
+                }
+            }
             if parts.is_empty() {
                 node.description.clone()
             } else {
@@ -254,6 +430,11 @@ fn format_stat_preview(current_stats: &Stats, node: &PassiveNode) -> String {
         PassiveEffect::LifeLeech(pct) => format!("Leech {:.1}% of damage as life", pct * 100.0),
         PassiveEffect::ShieldLeech(pct) => format!("Leech {:.1}% of damage as shield", pct * 100.0),
         PassiveEffect::ShieldRegen(amt) => format!("Regenerate {:.0} shield per second", amt),
+        PassiveEffect::MoreDamage(pct) => format!(
+            "Damage: {:.0} -> {:.0}",
+            current_stats.damage,
+            current_stats.damage * (1.0 + pct)
+        ),
         _ => node.description.clone(),
     }
 }
@@ -262,8 +443,10 @@ pub fn toggle_passive_ui(
     keyboard: Res<ButtonInput<KeyCode>>,
     current_state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
+    view_state: Option<Res<PassiveTreeViewState>>,
 ) {
-    if keyboard.just_pressed(KeyCode::KeyP) {
+    let typing = view_state.is_some_and(|state| state.search_focused);
+    if keyboard.just_pressed(KeyCode::KeyP) && !typing {
         match current_state.get() {
             GameState::Playing => next_state.set(GameState::PassiveTree),
             GameState::PassiveTree => next_state.set(GameState::Playing),
@@ -275,11 +458,13 @@ pub fn toggle_passive_ui(
 pub fn setup_passive_ui(
     mut commands: Commands,
     passive_tree: Res<PassiveTree>,
+    accessibility: Res<Accessibility>,
     player_query: Query<&PlayerPassives, With<Player>>,
 ) {
     let Ok(player_passives) = player_query.get_single() else {
         return;
     };
+    let palette = accessibility.palette;
     let points = player_passives.points;
 
     commands.insert_resource(PassiveTreeViewState::default());
@@ -360,6 +545,87 @@ pub fn setup_passive_ui(
                                 },
                             ));
                         });
+
+                    header
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                                    border: UiRect::all(Val::Px(2.0)),
+                                    ..default()
+                                },
+                                background_color: Color::srgb(0.2, 0.2, 0.25).into(),
+                                border_color: Color::srgb(0.5, 0.35, 0.6).into(),
+                                ..default()
+                            },
+                            RespecButton,
+                        ))
+                        .with_children(|btn| {
+                            btn.spawn((
+                                TextBundle::from_section(
+                                    format!("Respec ({})", player_passives.respec_charges),
+                                    TextStyle {
+                                        font_size: 16.0,
+                                        color: Color::WHITE,
+                                        ..default()
+                                    },
+                                ),
+                                RespecButtonText,
+                            ));
+                        });
+
+                    header
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                                    border: UiRect::all(Val::Px(2.0)),
+                                    ..default()
+                                },
+                                background_color: Color::srgb(0.2, 0.2, 0.25).into(),
+                                border_color: Color::srgb(0.4, 0.4, 0.5).into(),
+                                ..default()
+                            },
+                            ExportBuildButton,
+                        ))
+                        .with_children(|btn| {
+                            btn.spawn(TextBundle::from_section(
+                                "Export Build",
+                                TextStyle {
+                                    font_size: 16.0,
+                                    color: Color::WHITE,
+                                    ..default()
+                                },
+                            ));
+                        });
+
+                    header
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                                    border: UiRect::all(Val::Px(2.0)),
+                                    ..default()
+                                },
+                                background_color: Color::srgb(0.2, 0.2, 0.25).into(),
+                                border_color: Color::srgb(0.4, 0.6, 0.8).into(),
+                                ..default()
+                            },
+                            PlanningButton,
+                        ))
+                        .with_children(|btn| {
+                            btn.spawn((
+                                TextBundle::from_section(
+                                    "Plan: OFF",
+                                    TextStyle {
+                                        font_size: 16.0,
+                                        color: Color::WHITE,
+                                        ..default()
+                                    },
+                                ),
+                                PlanningButtonText,
+                            ));
+                        });
                 });
 
             parent
@@ -373,10 +639,38 @@ pub fn setup_passive_ui(
                     ..default()
                 },))
                 .with_children(|legend| {
-                    spawn_legend_item(legend, "Damage", NodeCategory::Damage.base_color());
-                    spawn_legend_item(legend, "Defense", NodeCategory::Defense.base_color());
-                    spawn_legend_item(legend, "Utility", NodeCategory::Utility.base_color());
-                    spawn_legend_item(legend, "Elemental", NodeCategory::Elemental.base_color());
+                    legend
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    width: Val::Px(220.0),
+                                    padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                                    border: UiRect::all(Val::Px(1.0)),
+                                    ..default()
+                                },
+                                background_color: Color::srgb(0.1, 0.1, 0.13).into(),
+                                border_color: Color::srgb(0.4, 0.4, 0.5).into(),
+                                ..default()
+                            },
+                            SearchBox,
+                        ))
+                        .with_children(|search| {
+                            search.spawn((
+                                TextBundle::from_section(
+                                    "Search...",
+                                    TextStyle {
+                                        font_size: 13.0,
+                                        color: Color::srgb(0.5, 0.5, 0.55),
+                                        ..default()
+                                    },
+                                ),
+                                SearchText,
+                            ));
+                        });
+                    spawn_legend_item(legend, palette, NodeCategory::Damage);
+                    spawn_legend_item(legend, palette, NodeCategory::Defense);
+                    spawn_legend_item(legend, palette, NodeCategory::Utility);
+                    spawn_legend_item(legend, palette, NodeCategory::Elemental);
                 });
 
             parent
@@ -426,7 +720,7 @@ pub fn setup_passive_ui(
 
                     for (&id, node) in &passive_tree.nodes {
                         let category = get_node_category(id, &node.effect);
-                        let node_size = if id == 0 { 45.0 } else { 36.0 };
+                        let node_size = node.tier.size();
 
                         map.spawn((
                             ButtonBundle {
@@ -441,7 +735,7 @@ pub fn setup_passive_ui(
                                     align_items: AlignItems::Center,
                                     ..default()
                                 },
-                                background_color: category.locked_color().into(),
+                                background_color: category.locked_color(palette).into(),
                                 border_color: Color::srgb(0.3, 0.3, 0.35).into(),
                                 ..default()
                             },
@@ -513,6 +807,14 @@ pub fn setup_passive_ui(
                                     ..default()
                                 },
                             ),
+                            TextSection::new(
+                                "",
+                                TextStyle {
+                                    font_size: 14.0,
+                                    color: Color::srgb(1.0, 0.4, 0.35),
+                                    ..default()
+                                },
+                            ),
                         ]),
                         PassiveTooltipText,
                     ));
@@ -529,8 +831,19 @@ pub fn setup_passive_ui(
                     ..default()
                 },))
                 .with_children(|controls| {
+                    controls.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 14.0,
+                                color: Color::srgb(0.9, 0.85, 0.5),
+                                ..default()
+                            },
+                        ),
+                        BuildCodeText,
+                    ));
                     controls.spawn(TextBundle::from_section(
-                        "Scroll: Zoom | Drag/WASD: Pan | P: Close",
+                        "Scroll: Zoom | Drag/WASD: Pan | Shift+Click: Path | RMB: Refund | P: Close",
                         TextStyle {
                             font_size: 14.0,
                             color: Color::srgb(0.5, 0.5, 0.55),
@@ -541,17 +854,24 @@ pub fn setup_passive_ui(
         });
 }
 
-fn spawn_legend_item(parent: &mut ChildBuilder, label: &str, color: Color) {
+fn spawn_legend_item(parent: &mut ChildBuilder, palette: ColorPalette, category: NodeCategory) {
+    let label = category.name();
+    let color = category.base_color(palette);
     parent
-        .spawn(NodeBundle {
-            style: Style {
-                flex_direction: FlexDirection::Row,
-                align_items: AlignItems::Center,
-                column_gap: Val::Px(6.0),
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(6.0),
+                    padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                    ..default()
+                },
+                background_color: Color::NONE.into(),
                 ..default()
             },
-            ..default()
-        })
+            LegendFilterButton(category),
+        ))
         .with_children(|item| {
             item.spawn(NodeBundle {
                 style: Style {
@@ -587,7 +907,11 @@ pub fn handle_passive_tree_controls(
         (Changed<Interaction>, With<CenterViewButton>),
     >,
 ) {
-    let pan_speed = 300.0 * time.delta_seconds();
+    let pan_speed = if view_state.search_focused {
+        0.0
+    } else {
+        300.0 * time.delta_seconds()
+    };
 
     if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) {
         view_state.pan_offset.y -= pan_speed;
@@ -650,9 +974,10 @@ pub fn handle_passive_tree_controls(
 }
 
 pub fn update_passive_ui(
-    player_query: Query<(&PlayerPassives, &Stats), With<Player>>,
+    player_query: Query<(&Player, &PlayerPassives, &Stats)>,
     passive_tree: Res<PassiveTree>,
     view_state: Res<PassiveTreeViewState>,
+    accessibility: Res<Accessibility>,
     mut points_text: Query<&mut Text, (With<PassivePointsText>, Without<PassiveTooltipText>)>,
     mut node_buttons: Query<
         (
@@ -670,9 +995,10 @@ pub fn update_passive_ui(
     >,
     mut tooltip_text: Query<&mut Text, (With<PassiveTooltipText>, Without<PassivePointsText>)>,
 ) {
-    let Ok((passives, stats)) = player_query.get_single() else {
+    let Ok((player, passives, stats)) = player_query.get_single() else {
         return;
     };
+    let palette = accessibility.palette;
 
     if let Ok(mut text) = points_text.get_single_mut() {
         text.sections[0].value = format!("Points: {}", passives.points);
@@ -680,8 +1006,17 @@ pub fn update_passive_ui(
 
     let mut hovered_node: Option<&PassiveNode> = None;
     let mut hovered_node_id: Option<u32> = None;
+    let allocated = if view_state.planning {
+        [
+            passives.unlocked_nodes.as_slice(),
+            passives.planned.as_slice(),
+        ]
+        .concat()
+    } else {
+        passives.unlocked_nodes.clone()
+    };
     let path_nodes: HashSet<u32> = if let Some(hovered_id) = view_state.hovered_node_id {
-        find_path_to_node(hovered_id, &passive_tree, &passives.unlocked_nodes)
+        find_path_to_node(hovered_id, &passive_tree, &allocated, player.class)
             .into_iter()
             .collect()
     } else {
@@ -699,14 +1034,9 @@ pub fn update_passive_ui(
         let node = passive_tree.nodes.get(&id).unwrap();
         let category = get_node_category(id, &node.effect);
         let can_unlock = passives.points > 0
-            && !is_unlocked
-            && (id == 0
-                || node
-                    .requirements
-                    .iter()
-                    .any(|req| passives.unlocked_nodes.contains(req)));
-
-        let node_size = if id == 0 { 45.0 } else { 36.0 };
+            && passive_tree.can_allocate(id, &passives.unlocked_nodes, player.class);
+
+        let node_size = node.tier.size();
         let scaled_size = node_size * zoom;
         let base_x = node.position.x + 450.0 - node_size / 2.0;
         let base_y = -node.position.y + 250.0 - node_size / 2.0;
@@ -719,10 +1049,13 @@ pub fn update_passive_ui(
         let is_in_path = path_nodes.contains(&id);
 
         if is_unlocked {
-            *bg_color = category.glow_color().into();
+            *bg_color = category.glow_color(palette).into();
             *border_color = Color::srgb(1.0, 0.95, 0.7).into();
+        } else if passives.planned.contains(&id) {
+            *bg_color = category.base_color(palette).with_alpha(0.4).into();
+            *border_color = Color::srgba(0.6, 0.8, 1.0, 0.8).into();
         } else if can_unlock {
-            let base = category.base_color();
+            let base = category.base_color(palette);
             match *interaction {
                 Interaction::Hovered => {
                     *bg_color = base.into();
@@ -739,7 +1072,7 @@ pub fn update_passive_ui(
             }
             *border_color = Color::srgb(0.5, 1.0, 0.5).into();
         } else {
-            let locked = category.locked_color();
+            let locked = category.locked_color(palette);
             if is_in_path && !passives.unlocked_nodes.contains(&id) {
                 *bg_color = Color::srgba(
                     locked.to_srgba().red * 1.5,
@@ -755,6 +1088,15 @@ pub fn update_passive_ui(
             }
         }
 
+        if view_state.hidden_categories.contains(&category) {
+            bg_color.0 = bg_color.0.with_alpha(0.15);
+            *border_color = Color::srgba(0.25, 0.25, 0.3, 0.3).into();
+        } else if !view_state.search.trim().is_empty()
+            && node_matches_search(node, category, &view_state.search)
+        {
+            *border_color = Color::srgb(0.3, 1.0, 1.0).into();
+        }
+
         if *interaction == Interaction::Hovered {
             hovered_node = Some(node);
             hovered_node_id = Some(id);
@@ -807,12 +1149,30 @@ pub fn update_passive_ui(
                 .unwrap_or(NodeCategory::Utility);
 
             text.sections[0].value = node.name.clone();
-            text.sections[0].style.color = category.base_color();
-            text.sections[2].value = node.description.clone();
-
-            if !is_unlocked {
+            text.sections[0].style.color = category.base_color(palette);
+            text.sections[2].value = match node.tier {
+                NodeTier::Minor => node.description.clone(),
+                NodeTier::Notable => format!("Notable - {}", node.description),
+                NodeTier::Keystone => format!("Keystone - {}", node.description),
+            };
+            text.sections[5].value = node
+                .drawback
+                .as_ref()
+                .map(|drawback| format!("\nDrawback: {}", drawback))
+                .unwrap_or_default();
+
+            if let Some(class) = node.class.filter(|&c| c != player.class) {
+                text.sections[4].value = format!("{:?} only", class);
+                text.sections[4].style.color = Color::srgb(0.8, 0.4, 0.4);
+            } else if !is_unlocked {
                 let preview = format_stat_preview(stats, node);
                 text.sections[4].value = format!("Preview: {}", preview);
+                text.sections[4].style.color = Color::srgb(0.5, 0.9, 0.5);
+            } else if hovered_node_id
+                .is_some_and(|id| passive_tree.can_refund(id, &passives.unlocked_nodes))
+            {
+                text.sections[4].value = "(Unlocked) Right-click to refund".to_string();
+                text.sections[4].style.color = Color::srgb(0.7, 0.7, 0.7);
             } else {
                 text.sections[4].value = "(Unlocked)".to_string();
                 text.sections[4].style.color = Color::srgb(0.7, 0.7, 0.7);
@@ -822,6 +1182,7 @@ pub fn update_passive_ui(
             text.sections[0].style.color = Color::srgb(0.6, 0.6, 0.6);
             text.sections[2].value = "Click to unlock available nodes".to_string();
             text.sections[4].value = "".to_string();
+            text.sections[5].value = "".to_string();
         }
     }
 }
@@ -841,47 +1202,273 @@ pub fn track_hovered_node(
 }
 
 pub fn handle_node_click(
-    mut player_query: Query<(&mut PlayerPassives, &mut Stats), With<Player>>,
+    mut player_query: Query<(&Player, &mut PlayerPassives)>,
     passive_tree: Res<PassiveTree>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    view_state: Res<PassiveTreeViewState>,
     mut interaction_query: Query<
         (&Interaction, &PassiveNodeButton),
         (Changed<Interaction>, With<Button>),
     >,
 ) {
-    let Ok((mut passives, mut stats)) = player_query.get_single_mut() else {
+    let Ok((player, mut passives)) = player_query.get_single_mut() else {
         return;
     };
 
+    let refund = view_state.hovered_node_id.filter(|id| {
+        mouse.just_pressed(MouseButton::Right)
+            && passives.unlocked_nodes.contains(id)
+            && passive_tree.can_refund(*id, &passives.unlocked_nodes)
+    });
+    if let Some(node_id) = refund {
+        passives.unlocked_nodes.retain(|&id| id != node_id);
+        passives.points += 1;
+    }
+
+    let shift = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+
     for (interaction, node_btn) in interaction_query.iter_mut() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
         let node_id = node_btn.0;
-        let is_unlocked = passives.unlocked_nodes.contains(&node_id);
 
-        let node = passive_tree.nodes.get(&node_id).unwrap();
-        let can_unlock = passives.points > 0
-            && !is_unlocked
-            && (node_id == 0
-                || node
-                    .requirements
-                    .iter()
-                    .any(|req| passives.unlocked_nodes.contains(req)));
-
-        if *interaction == Interaction::Pressed && can_unlock {
+        if view_state.planning {
+            if let Some(index) = passives.planned.iter().position(|&id| id == node_id) {
+                passives.planned.truncate(index);
+            } else if !passives.unlocked_nodes.contains(&node_id) {
+                let allocated = [
+                    passives.unlocked_nodes.as_slice(),
+                    passives.planned.as_slice(),
+                ]
+                .concat();
+                let path = nodes_to_allocate(node_id, &passive_tree, &allocated, player.class);
+                passives.planned.extend(path);
+            }
+            continue;
+        }
+
+        if shift {
+            let path = nodes_to_allocate(
+                node_id,
+                &passive_tree,
+                &passives.unlocked_nodes,
+                player.class,
+            );
+            if !path.is_empty() && passives.points as usize >= path.len() {
+                passives.points -= path.len() as u32;
+                passives.unlocked_nodes.extend(path);
+            }
+        } else if passives.points > 0
+            && passive_tree.can_allocate(node_id, &passives.unlocked_nodes, player.class)
+        {
             passives.unlocked_nodes.push(node_id);
             passives.points -= 1;
+        }
+    }
+}
 
-            match node.effect {
-                PassiveEffect::StatAdd(s) => {
-                    stats.damage += s.damage;
-                    stats.speed += s.speed;
-                    stats.attack_speed += s.attack_speed;
-                    stats.crit_chance += s.crit_chance;
-                    stats.crit_multiplier += s.crit_multiplier;
-                    stats.life_regen += s.life_regen;
-                    stats.armor += s.armor;
+pub fn handle_planning_button(
+    mut view_state: ResMut<PassiveTreeViewState>,
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<PlanningButton>),
+    >,
+    mut text_query: Query<&mut Text, With<PlanningButtonText>>,
+) {
+    for (interaction, mut bg_color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                view_state.planning = !view_state.planning;
+                *bg_color = Color::srgb(0.3, 0.3, 0.35).into();
+            }
+            Interaction::Hovered => {
+                *bg_color = Color::srgb(0.25, 0.25, 0.3).into();
+            }
+            Interaction::None => {
+                *bg_color = Color::srgb(0.2, 0.2, 0.25).into();
+            }
+        }
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = if view_state.planning {
+            "Plan: ON".to_string()
+        } else {
+            "Plan: OFF".to_string()
+        };
+    }
+}
+
+pub fn apply_planned_allocations(
+    passive_tree: Res<PassiveTree>,
+    mut players: Query<(&Player, &mut PlayerPassives)>,
+) {
+    for (player, mut passives) in players.iter_mut() {
+        while passives.points > 0 && !passives.planned.is_empty() {
+            let next = passives.planned.remove(0);
+            if passives.unlocked_nodes.contains(&next) {
+                continue;
+            }
+            if !passive_tree.can_allocate(next, &passives.unlocked_nodes, player.class) {
+                // The rest of the plan hangs off this node, so it is no longer reachable.
+                passives.planned.clear();
+                break;
+            }
+            passives.unlocked_nodes.push(next);
+            passives.points -= 1;
+        }
+    }
+}
+
+pub fn handle_respec_button(
+    mut player_query: Query<&mut PlayerPassives, With<Player>>,
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<RespecButton>),
+    >,
+    mut text_query: Query<&mut Text, With<RespecButtonText>>,
+) {
+    let Ok(mut passives) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for (interaction, mut bg_color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                if passives.respec_charges > 0 && !passives.unlocked_nodes.is_empty() {
+                    passives.respec_charges -= 1;
+                    passives.points += passives.unlocked_nodes.len() as u32;
+                    passives.unlocked_nodes.clear();
                 }
-                _ => {}
+                *bg_color = Color::srgb(0.3, 0.3, 0.35).into();
+            }
+            Interaction::Hovered => {
+                *bg_color = Color::srgb(0.25, 0.25, 0.3).into();
+            }
+            Interaction::None => {
+                *bg_color = Color::srgb(0.2, 0.2, 0.25).into();
+            }
+        }
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = format!("Respec ({})", passives.respec_charges);
+    }
+}
+
+pub fn handle_passive_search(
+    mut view_state: ResMut<PassiveTreeViewState>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    search_box: Query<&Interaction, (Changed<Interaction>, With<SearchBox>)>,
+    mut search_text: Query<&mut Text, With<SearchText>>,
+) {
+    if search_box.iter().any(|i| *i == Interaction::Pressed) {
+        view_state.search_focused = true;
+    }
+
+    if !view_state.search_focused {
+        keyboard_events.clear();
+    } else if keyboard.just_pressed(KeyCode::Escape) {
+        view_state.search.clear();
+        view_state.search_focused = false;
+        keyboard_events.clear();
+    } else if keyboard.just_pressed(KeyCode::Enter) {
+        view_state.search_focused = false;
+        keyboard_events.clear();
+    } else {
+        read_text_input(&mut keyboard_events, &mut view_state.search, 24);
+    }
+
+    if let Ok(mut text) = search_text.get_single_mut() {
+        let section = &mut text.sections[0];
+        if view_state.search_focused {
+            section.value = format!("{}|", view_state.search);
+            section.style.color = Color::WHITE;
+        } else if view_state.search.is_empty() {
+            section.value = "Search...".to_string();
+            section.style.color = Color::srgb(0.5, 0.5, 0.55);
+        } else {
+            section.value = view_state.search.clone();
+            section.style.color = Color::srgb(0.3, 1.0, 1.0);
+        }
+    }
+}
+
+pub fn handle_legend_filters(
+    mut view_state: ResMut<PassiveTreeViewState>,
+    mut legend_buttons: Query<
+        (&Interaction, &LegendFilterButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, filter, mut bg_color) in legend_buttons.iter_mut() {
+        let category = filter.0;
+        if *interaction == Interaction::Pressed {
+            if let Some(index) = view_state
+                .hidden_categories
+                .iter()
+                .position(|c| *c == category)
+            {
+                view_state.hidden_categories.remove(index);
+            } else {
+                view_state.hidden_categories.push(category);
             }
         }
+        let hidden = view_state.hidden_categories.contains(&category);
+        *bg_color = match (*interaction, hidden) {
+            (_, true) => Color::srgba(0.3, 0.1, 0.1, 0.6).into(),
+            (Interaction::Hovered, false) => Color::srgba(1.0, 1.0, 1.0, 0.08).into(),
+            _ => Color::NONE.into(),
+        };
+    }
+}
+
+pub fn handle_export_build(
+    mut commands: Commands,
+    player_query: Query<(&Player, &PlayerPassives)>,
+    button_query: Query<&Interaction, (Changed<Interaction>, With<ExportBuildButton>)>,
+    mut text_query: Query<(Entity, &mut Text), With<BuildCodeText>>,
+) {
+    if !button_query.iter().any(|i| *i == Interaction::Pressed) {
+        return;
+    }
+    let Ok((player, passives)) = player_query.get_single() else {
+        return;
+    };
+
+    let code = BuildCode {
+        class: player.class,
+        nodes: [
+            passives.unlocked_nodes.as_slice(),
+            passives.planned.as_slice(),
+        ]
+        .concat(),
+    }
+    .encode();
+    info!("Passive build code: {code}");
+    if let Ok((entity, mut text)) = text_query.get_single_mut() {
+        text.sections[0].value = format!("Build code: {code}");
+        commands
+            .entity(entity)
+            .insert(ClipboardCopy(copy_to_clipboard(code)));
+    }
+}
+
+pub fn finish_build_code_copy(
+    mut commands: Commands,
+    mut copies: Query<(Entity, &mut Text, &mut ClipboardCopy)>,
+) {
+    for (entity, mut text, mut task) in copies.iter_mut() {
+        let Some(copied) = poll_clipboard(&mut task.0) else {
+            continue;
+        };
+        commands.entity(entity).remove::<ClipboardCopy>();
+        if copied {
+            text.sections[0].value.push_str(" (copied to clipboard)");
+        }
     }
 }
 
@@ -0,0 +1,336 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::components::*;
+use crate::events::*;
+use crate::resources::*;
+
+const BUTTON_COLOR: Color = Color::srgb(0.2, 0.2, 0.25);
+const BUTTON_HOVER_COLOR: Color = Color::srgb(0.25, 0.25, 0.3);
+const BUTTON_PRESSED_COLOR: Color = Color::srgb(0.3, 0.3, 0.35);
+
+#[derive(Resource, Default)]
+pub struct PauseMenuState {
+    pub settings_open: bool,
+}
+
+pub fn toggle_pause(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut menu: ResMut<PauseMenuState>,
+) {
+    if !keyboard.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    match current_state.get() {
+        GameState::Playing => next_state.set(GameState::Paused),
+        GameState::Paused if menu.settings_open => menu.settings_open = false,
+        GameState::Paused => next_state.set(GameState::Playing),
+        _ => {}
+    }
+}
+
+pub fn pause_virtual_time(mut time: ResMut<Time<Virtual>>, mut menu: ResMut<PauseMenuState>) {
+    time.pause();
+    menu.settings_open = false;
+}
+
+pub fn resume_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn spawn_menu_button(parent: &mut ChildBuilder, label: &str, width: f32, bundle: impl Bundle) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(width),
+                    height: Val::Px(40.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BUTTON_COLOR.into(),
+                ..default()
+            },
+            bundle,
+        ))
+        .with_children(|btn| {
+            btn.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn setting_label(settings: &Settings, accessibility: &Accessibility, kind: SettingKind) -> String {
+    if kind.is_accessibility() {
+        accessibility.value_label(kind)
+    } else {
+        settings.value_label(kind)
+    }
+}
+
+pub fn setup_pause_menu(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    accessibility: Res<Accessibility>,
+) {
+    commands
+        .spawn((
+            PauseMenuUi,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.7).into(),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "PAUSED",
+                TextStyle {
+                    font_size: 48.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn((
+                    PauseMainPanel,
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::top(Val::Px(30.0)),
+                            row_gap: Val::Px(12.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                ))
+                .with_children(|panel| {
+                    for (action, label) in [
+                        (PauseAction::Resume, "Resume"),
+                        (PauseAction::Settings, "Settings"),
+                        (PauseAction::Restart, "Restart"),
+                        (PauseAction::QuitToClassSelect, "Quit to Class Select"),
+                    ] {
+                        spawn_menu_button(panel, label, 260.0, action);
+                    }
+                });
+
+            parent
+                .spawn((
+                    SettingsPanel,
+                    NodeBundle {
+                        style: Style {
+                            display: Display::None,
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::top(Val::Px(30.0)),
+                            row_gap: Val::Px(10.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                ))
+                .with_children(|panel| {
+                    for (kind, label) in [
+                        (SettingKind::Volume, "Volume"),
+                        (SettingKind::DamageNumbers, "Damage Numbers"),
+                        (SettingKind::WindowMode, "Window Mode"),
+                        (SettingKind::ScreenShake, "Screen Shake"),
+                        (SettingKind::ZoomPunch, "Zoom Punch"),
+                        (SettingKind::HitStop, "Hit Stop"),
+                        (SettingKind::SlowMotion, "Slow Motion"),
+                        (SettingKind::Palette, "Color Palette"),
+                        (SettingKind::HighContrast, "High Contrast"),
+                    ] {
+                        let toggle = kind.is_toggle();
+                        panel
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Row,
+                                    align_items: AlignItems::Center,
+                                    column_gap: Val::Px(10.0),
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|row| {
+                                row.spawn(
+                                    TextBundle::from_section(
+                                        label,
+                                        TextStyle {
+                                            font_size: 20.0,
+                                            color: Color::srgb(0.8, 0.8, 0.8),
+                                            ..default()
+                                        },
+                                    )
+                                    .with_style(Style {
+                                        width: Val::Px(180.0),
+                                        ..default()
+                                    }),
+                                );
+                                if !toggle {
+                                    spawn_menu_button(
+                                        row,
+                                        "-",
+                                        40.0,
+                                        SettingButton { kind, step: -1.0 },
+                                    );
+                                }
+                                row.spawn((
+                                    TextBundle::from_section(
+                                        setting_label(&settings, &accessibility, kind),
+                                        TextStyle {
+                                            font_size: 20.0,
+                                            color: Color::WHITE,
+                                            ..default()
+                                        },
+                                    )
+                                    .with_style(Style {
+                                        width: Val::Px(110.0),
+                                        ..default()
+                                    })
+                                    .with_text_justify(JustifyText::Center),
+                                    SettingValueText(kind),
+                                ));
+                                let label = if toggle { "Change" } else { "+" };
+                                let width = if toggle { 90.0 } else { 40.0 };
+                                spawn_menu_button(
+                                    row,
+                                    label,
+                                    width,
+                                    SettingButton { kind, step: 1.0 },
+                                );
+                            });
+                    }
+                    spawn_menu_button(panel, "Back", 260.0, PauseAction::Back);
+                });
+        });
+}
+
+pub fn despawn_pause_menu(mut commands: Commands, query: Query<Entity, With<PauseMenuUi>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn handle_pause_buttons(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut menu: ResMut<PauseMenuState>,
+    mut restart_events: EventWriter<RestartRunEvent>,
+    mut buttons: Query<
+        (&Interaction, &PauseAction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, action, mut bg_color) in buttons.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = BUTTON_PRESSED_COLOR.into();
+                match action {
+                    PauseAction::Resume => next_state.set(GameState::Playing),
+                    PauseAction::Settings => menu.settings_open = true,
+                    PauseAction::Back => menu.settings_open = false,
+                    PauseAction::Restart => {
+                        restart_events.send(RestartRunEvent {
+                            keep_selection: true,
+                        });
+                    }
+                    PauseAction::QuitToClassSelect => {
+                        restart_events.send(RestartRunEvent {
+                            keep_selection: false,
+                        });
+                    }
+                }
+            }
+            Interaction::Hovered => *bg_color = BUTTON_HOVER_COLOR.into(),
+            Interaction::None => *bg_color = BUTTON_COLOR.into(),
+        }
+    }
+}
+
+pub fn handle_setting_buttons(
+    mut settings: ResMut<Settings>,
+    mut accessibility: ResMut<Accessibility>,
+    mut buttons: Query<
+        (&Interaction, &SettingButton, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, button, mut bg_color) in buttons.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = BUTTON_PRESSED_COLOR.into();
+                if button.kind.is_accessibility() {
+                    accessibility.adjust(button.kind, button.step);
+                    accessibility.save();
+                } else {
+                    settings.adjust(button.kind, button.step);
+                    settings.save();
+                }
+            }
+            Interaction::Hovered => *bg_color = BUTTON_HOVER_COLOR.into(),
+            Interaction::None => *bg_color = BUTTON_COLOR.into(),
+        }
+    }
+}
+
+pub fn update_pause_menu(
+    menu: Res<PauseMenuState>,
+    settings: Res<Settings>,
+    accessibility: Res<Accessibility>,
+    mut main_panel: Query<&mut Style, (With<PauseMainPanel>, Without<SettingsPanel>)>,
+    mut settings_panel: Query<&mut Style, With<SettingsPanel>>,
+    mut value_texts: Query<(&mut Text, &SettingValueText)>,
+) {
+    let (main_display, settings_display) = if menu.settings_open {
+        (Display::None, Display::Flex)
+    } else {
+        (Display::Flex, Display::None)
+    };
+    for mut style in main_panel.iter_mut() {
+        style.display = main_display;
+    }
+    for mut style in settings_panel.iter_mut() {
+        style.display = settings_display;
+    }
+
+    if settings.is_changed() || accessibility.is_changed() {
+        for (mut text, value) in value_texts.iter_mut() {
+            text.sections[0].value = setting_label(&settings, &accessibility, value.0);
+        }
+    }
+}
+
+pub fn apply_window_mode(
+    settings: Res<Settings>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    for mut window in windows.iter_mut() {
+        let mode = settings.window_mode.window_mode();
+        if window.mode != mode {
+            window.mode = mode;
+        }
+    }
+}
@@ -1,8 +1,15 @@
 use crate::components::*;
+use crate::constants::*;
 use crate::events::*;
+use crate::resources::*;
 use bevy::prelude::*;
 
-pub fn spawn_pet(commands: &mut Commands, owner: Entity, pet_type: PetType, offset: Vec2) {
+pub fn spawn_pet(
+    commands: &mut Commands,
+    owner: Entity,
+    pet_type: PetType,
+    offset: Vec2,
+) -> Entity {
     let color = match pet_type {
         PetType::Healer => Color::srgb(0.2, 1.0, 0.5),
         PetType::Damager => Color::srgb(1.0, 0.2, 0.2),
@@ -14,7 +21,7 @@ pub fn spawn_pet(commands: &mut Commands, owner: Entity, pet_type: PetType, offs
         SpriteBundle {
             sprite: Sprite {
                 color,
-                custom_size: Some(Vec2::splat(16.0)),
+                custom_size: Some(Vec2::splat(PET_SIZE)),
                 ..default()
             },
             transform: Transform::from_translation(offset.extend(8.0)),
@@ -25,11 +32,142 @@ pub fn spawn_pet(commands: &mut Commands, owner: Entity, pet_type: PetType, offs
             pet_type,
             action_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
         },
+        Health {
+            current: PET_BASE_LIFE,
+            max: PET_BASE_LIFE,
+        },
     ));
 
     if pet_type == PetType::Tanker {
         entity.insert(Taunt);
     }
+    entity.with_children(|parent| {
+        parent.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgb(0.15, 0.0, 0.0),
+                    custom_size: Some(Vec2::new(PET_SIZE + 8.0, 4.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, PET_SIZE / 2.0 + 8.0, 0.1),
+                ..default()
+            },
+            HealthBar,
+        ));
+        parent.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgb(0.1, 0.9, 0.1),
+                    custom_size: Some(Vec2::new(PET_SIZE + 6.0, 2.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, PET_SIZE / 2.0 + 8.0, 0.2),
+                ..default()
+            },
+            HealthBarFill(PET_SIZE + 6.0),
+        ));
+    });
+    entity.id()
+}
+
+/// Keeps pet max life in line with the owner's pet life bonuses, preserving the fill ratio.
+pub fn sync_pet_life(
+    passive_tree: Res<PassiveTree>,
+    owners: Query<&PlayerPassives, With<Player>>,
+    mut pets: Query<(&Pet, &mut Health)>,
+) {
+    for (pet, mut health) in pets.iter_mut() {
+        let Ok(passives) = owners.get(pet.owner) else {
+            continue;
+        };
+        let max = PET_BASE_LIFE * (1.0 + passive_tree.bonuses(&passives.unlocked_nodes).pet_life);
+        if health.max != max {
+            health.current *= max / health.max;
+            health.max = max;
+        }
+    }
+}
+
+pub fn update_downed_pets(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pets: Query<(
+        Entity,
+        &Pet,
+        &mut Health,
+        &mut Visibility,
+        Option<&mut PetDowned>,
+    )>,
+) {
+    for (entity, pet, mut health, mut visibility, downed) in pets.iter_mut() {
+        match downed {
+            None if health.current <= 0.0 => {
+                health.current = 0.0;
+                *visibility = Visibility::Hidden;
+                commands
+                    .entity(entity)
+                    .remove::<Taunt>()
+                    .insert(PetDowned(Timer::from_seconds(
+                        PET_REVIVE_TIME,
+                        TimerMode::Once,
+                    )));
+            }
+            Some(mut downed) => {
+                downed.0.tick(time.delta());
+                if !downed.0.finished() {
+                    continue;
+                }
+                health.current = health.max;
+                *visibility = Visibility::Inherited;
+                let mut entity_commands = commands.entity(entity);
+                entity_commands.remove::<PetDowned>();
+                if pet.pet_type == PetType::Tanker {
+                    entity_commands.insert(Taunt);
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+pub fn sync_bonus_pets(
+    mut commands: Commands,
+    passive_tree: Res<PassiveTree>,
+    players: Query<
+        (Entity, &Transform, &PlayerPassives, Option<&TamerData>),
+        Changed<PlayerPassives>,
+    >,
+    bonus_pets: Query<(Entity, &Pet), With<BonusPet>>,
+) {
+    for (player_entity, transform, passives, tamer) in players.iter() {
+        let wanted = passive_tree.bonuses(&passives.unlocked_nodes).extra_pets as usize;
+        let owned: Vec<Entity> = bonus_pets
+            .iter()
+            .filter(|(_, pet)| pet.owner == player_entity)
+            .map(|(entity, _)| entity)
+            .collect();
+
+        for &entity in owned.iter().skip(wanted) {
+            commands.entity(entity).despawn();
+        }
+        let selected = tamer
+            .map(|data| data.selected_pets.as_slice())
+            .unwrap_or(&[]);
+        for i in owned.len()..wanted {
+            let pet_type = if selected.is_empty() {
+                PetType::Damager
+            } else {
+                selected[i % selected.len()]
+            };
+            let pet = spawn_pet(
+                &mut commands,
+                player_entity,
+                pet_type,
+                transform.translation.truncate(),
+            );
+            commands.entity(pet).insert(BonusPet);
+        }
+    }
 }
 
 pub fn update_pets(
@@ -63,19 +201,25 @@ pub fn update_pets(
 
 pub fn pet_actions(
     time: Res<Time>,
-    mut pet_query: Query<(Entity, &Transform, &mut Pet)>,
-    mut owner_query: Query<(&mut Health, &mut Stats), With<Player>>,
+    mut pet_query: Query<(Entity, &Transform, &mut Pet), Without<PetDowned>>,
+    mut owner_query: Query<(&mut Health, &PlayerPassives), With<Player>>,
     enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    passive_tree: Res<PassiveTree>,
     mut damage_events: EventWriter<DamageEvent>,
 ) {
     for (pet_entity, transform, mut pet) in pet_query.iter_mut() {
         pet.action_timer.tick(time.delta());
 
         if pet.action_timer.just_finished() {
+            let bonuses = owner_query
+                .get(pet.owner)
+                .map(|(_, passives)| passive_tree.bonuses(&passives.unlocked_nodes))
+                .unwrap_or_default();
             match pet.pet_type {
                 PetType::Healer => {
                     if let Ok((mut health, _)) = owner_query.get_mut(pet.owner) {
-                        health.current = (health.current + health.max * 0.01).min(health.max);
+                        let heal = health.max * 0.01;
+                        health.current = (health.current + heal).min(health.max);
                     }
                 }
                 PetType::Damager => {
@@ -95,19 +239,34 @@ pub fn pet_actions(
                         damage_events.send(DamageEvent {
                             target,
                             attacker: Some(pet_entity),
-                            amount: 10.0,
-                            is_crit: false,
+                            amount: 10.0 * (1.0 + bonuses.pet_damage),
+                            damage_type: DamageType::Physical,
+                            source: DamageSource::Hit,
+                            kind: AttackKind::Pet,
+                            crit_bonus: 0.0,
                         });
                     }
                 }
-                PetType::Buffer => {
-                    if let Ok((_, mut stats)) = owner_query.get_mut(pet.owner) {
-                        stats.armor = (stats.armor).max(25.0);
-                        stats.damage = (stats.damage).max(35.0);
-                    }
-                }
-                PetType::Tanker => {}
+                // Buffer pets work through `PetBuffed`, applied in `recompute_player_stats`.
+                PetType::Buffer | PetType::Tanker => {}
             }
         }
     }
 }
+
+pub fn sync_pet_buffs(
+    mut commands: Commands,
+    players: Query<(Entity, Has<PetBuffed>), With<Player>>,
+    pets: Query<&Pet, Without<PetDowned>>,
+) {
+    for (player_entity, buffed) in players.iter() {
+        let wanted = pets
+            .iter()
+            .any(|pet| pet.owner == player_entity && pet.pet_type == PetType::Buffer);
+        if wanted && !buffed {
+            commands.entity(player_entity).insert(PetBuffed);
+        } else if !wanted && buffed {
+            commands.entity(player_entity).remove::<PetBuffed>();
+        }
+    }
+}
@@ -1,20 +1,34 @@
 use crate::components::*;
 use crate::constants::*;
+use crate::events::*;
 use crate::helpers::*;
 use crate::resources::*;
 use crate::systems::combat::spawn_melee_attack;
+use crate::systems::projectiles::{attach_projectile_behaviors, spawn_orbiting_projectile};
 use bevy::prelude::*;
-use rand::Rng;
 use std::collections::HashSet;
 
 pub fn player_movement(
     keyboard: Res<ButtonInput<KeyCode>>,
     time: Res<Time>,
     cursor_pos: Res<CursorWorldPos>,
-    mut query: Query<(&mut Transform, &Stats, Option<&Dash>, &mut CharacterState), With<Player>>,
+    mut query: Query<
+        (
+            &mut Transform,
+            &Stats,
+            Option<&Dash>,
+            Option<&AuraSlow>,
+            Option<&HazardSlow>,
+            &ElementalStatus,
+            &mut CharacterState,
+        ),
+        With<Player>,
+    >,
     obstacles: Query<(&Transform, &Sprite), (With<Obstacle>, Without<Player>, Without<Dash>)>,
 ) {
-    let Ok((mut transform, stats, dash, mut state)) = query.get_single_mut() else {
+    let Ok((mut transform, stats, dash, slow, hazard_slow, status, mut state)) =
+        query.get_single_mut()
+    else {
         return;
     };
     if dash.is_some() {
@@ -35,7 +49,10 @@ pub fn player_movement(
     }
     if direction.length_squared() > 0.0 {
         direction = direction.normalize();
-        let movement = direction * stats.speed * time.delta_seconds();
+        let slow_mult = (1.0 - slow.map(|s| s.0).unwrap_or(0.0))
+            * (1.0 - hazard_slow.map(|s| s.0).unwrap_or(0.0))
+            * (1.0 - status.chill_slow() * stats.cold_taken());
+        let movement = direction * stats.speed * slow_mult * time.delta_seconds();
         let mut new_pos = transform.translation + movement.extend(0.0);
 
         let mut collision = false;
@@ -148,17 +165,34 @@ pub fn player_attack(
             &Player,
             &PlayerPassives,
             &mut AttackCooldown,
+            &ElementalStatus,
             &mut CharacterState,
+            &mut MeleeCombo,
         ),
         With<Player>,
     >,
+    orbiters: Query<&Orbit>,
+    passive_tree: Res<PassiveTree>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
-    let Ok((player_entity, transform, stats, player, passives, mut cooldown, mut state)) =
-        query.get_single_mut()
+    let Ok((
+        player_entity,
+        transform,
+        stats,
+        player,
+        passives,
+        mut cooldown,
+        status,
+        mut state,
+        mut combo,
+    )) = query.get_single_mut()
     else {
         return;
     };
-    cooldown.0.tick(time.delta());
+    let chill_mult = 1.0 - status.chill_slow() * stats.cold_taken();
+    cooldown.0.tick(time.delta().mul_f32(chill_mult));
+    combo.bypass_change_detection().window.tick(time.delta());
     if cooldown.0.finished() && *state == CharacterState::Attacking {
         *state = CharacterState::Idle;
     }
@@ -166,24 +200,21 @@ pub fn player_attack(
         return;
     }
     let player_pos = transform.translation.truncate();
-    let direction = (cursor_pos.0 - player_pos).normalize_or_zero();
-    let mut rng = rand::thread_rng();
-    let is_crit = rng.r#gen::<f32>() < stats.crit_chance;
-    let damage = if is_crit {
-        stats.damage * stats.crit_multiplier
-    } else {
-        stats.damage
-    };
+    let direction = (cursor_pos.0 - player_pos).normalize_or(Vec2::X);
+    let damage = stats.damage;
     if mouse.pressed(MouseButton::Left) {
         *state = CharacterState::Attacking;
         if player.class == PlayerClass::Tank {
+            let step = combo.advance();
             spawn_melee_attack(
                 &mut commands,
+                &mut meshes,
+                &mut materials,
                 player_entity,
                 player_pos,
                 direction,
                 damage,
-                stats.attack_speed,
+                step,
                 true,
             );
             cooldown.0 = Timer::from_seconds(0.4 / stats.attack_speed, TimerMode::Once);
@@ -206,60 +237,64 @@ pub fn player_attack(
                 Vec2::new(24.0, 24.0)
             };
             let stretched_size = Vec2::new(base_size.x * 1.6, base_size.y);
-            commands.spawn((
-                SpriteBundle {
-                    texture: texture.unwrap_or_default(),
-                    sprite: Sprite {
-                        color: if is_crit {
-                            Color::srgb(1.0, 1.0, 0.2)
-                        } else {
-                            proj_color
+            let bonuses = passive_tree.bonuses(&passives.unlocked_nodes);
+            let extra = bonuses.extra_projectiles as f32;
+            for i in 0..=bonuses.extra_projectiles {
+                let offset = (i as f32 - extra / 2.0) * EXTRA_PROJECTILE_SPREAD;
+                let direction = Vec2::from_angle(offset).rotate(direction);
+                let projectile = commands
+                    .spawn((
+                        SpriteBundle {
+                            texture: texture.clone().unwrap_or_default(),
+                            sprite: Sprite {
+                                color: proj_color,
+                                custom_size: Some(stretched_size),
+                                ..default()
+                            },
+                            transform: Transform::from_translation(spawn_pos.extend(5.0))
+                                .with_rotation(Quat::from_rotation_z(
+                                    direction.y.atan2(direction.x),
+                                )),
+                            ..default()
                         },
-                        custom_size: Some(stretched_size),
-                        ..default()
-                    },
-                    transform: Transform::from_translation(spawn_pos.extend(5.0))
-                        .with_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))),
-                    ..default()
-                },
-                Projectile {
-                    damage,
-                    owner: player_entity,
-                    pierce: if player.class == PlayerClass::Archer {
-                        1
-                    } else {
-                        0
-                    },
-                    chain_count: if passives.unlocked_nodes.contains(&10) {
-                        1
-                    } else {
-                        0
-                    },
-                    hit_entities: HashSet::new(),
-                    is_crit,
-                },
-                Velocity(direction * PROJECTILE_SPEED),
-                Lifetime(Timer::from_seconds(PROJECTILE_LIFETIME, TimerMode::Once)),
-                ProjectileTrail {
-                    spawn_timer: Timer::from_seconds(0.02, TimerMode::Repeating),
-                    color: if is_crit {
-                        Color::srgba(1.0, 1.0, 0.2, 0.6)
-                    } else {
-                        proj_color.with_alpha(0.5)
-                    },
-                },
-            ));
-            let flash_color = if is_crit {
-                Color::srgba(1.0, 1.0, 0.5, 0.9)
-            } else {
-                Color::srgba(1.0, 0.9, 0.6, 0.8)
-            };
-            let flash_size = if is_crit { 45.0 } else { 30.0 };
+                        Projectile {
+                            damage,
+                            owner: player_entity,
+                            pierce: if player.class == PlayerClass::Archer {
+                                1
+                            } else {
+                                0
+                            } + bonuses.extra_pierce,
+                            hit_entities: HashSet::new(),
+                            crit_bonus: 0.0,
+                            kind: AttackKind::Primary,
+                            spent: false,
+                        },
+                        Velocity(direction * PROJECTILE_SPEED),
+                        Lifetime(Timer::from_seconds(PROJECTILE_LIFETIME, TimerMode::Once)),
+                        ProjectileTrail {
+                            spawn_timer: Timer::from_seconds(0.02, TimerMode::Repeating),
+                            color: proj_color.with_alpha(0.5),
+                        },
+                    ))
+                    .id();
+                attach_projectile_behaviors(&mut commands, projectile, &bonuses);
+            }
+            if bonuses.orbiting && orbiters.iter().count() < ORBIT_MAX_PROJECTILES {
+                let angle = direction.y.atan2(direction.x);
+                spawn_orbiting_projectile(
+                    &mut commands,
+                    player_entity,
+                    player_pos,
+                    damage * 0.5,
+                    angle,
+                );
+            }
             commands.spawn((
                 SpriteBundle {
                     sprite: Sprite {
-                        color: flash_color,
-                        custom_size: Some(Vec2::splat(flash_size)),
+                        color: Color::srgba(1.0, 0.9, 0.6, 0.8),
+                        custom_size: Some(Vec2::splat(30.0)),
                         ..default()
                     },
                     transform: Transform::from_translation(spawn_pos.extend(6.0)),
@@ -275,16 +310,18 @@ pub fn player_attack(
                 recoil_amount: 3.0,
             });
         }
-    }
-    if mouse.pressed(MouseButton::Right) {
+    } else if mouse.pressed(MouseButton::Right) {
         *state = CharacterState::Attacking;
+        let step = combo.advance();
         spawn_melee_attack(
             &mut commands,
+            &mut meshes,
+            &mut materials,
             player_entity,
             player_pos,
             direction,
             damage,
-            stats.attack_speed,
+            step,
             player.class == PlayerClass::Tank,
         );
         cooldown.0 = Timer::from_seconds(0.4 / stats.attack_speed, TimerMode::Once);
@@ -306,12 +343,22 @@ pub fn player_skills(
             &PlayerPassives,
             &mut SkillCooldowns,
             &mut Shield,
+            &mut Health,
         ),
         With<Player>,
     >,
+    passive_tree: Res<PassiveTree>,
 ) {
-    let Ok((player_entity, mut transform, stats, player, passives, mut cooldowns, mut shield)) =
-        query.get_single_mut()
+    let Ok((
+        player_entity,
+        mut transform,
+        stats,
+        player,
+        passives,
+        mut cooldowns,
+        mut shield,
+        mut health,
+    )) = query.get_single_mut()
     else {
         return;
     };
@@ -331,14 +378,16 @@ pub fn player_skills(
         ));
     }
     if keyboard.just_pressed(KeyCode::Space) && cooldowns.nova.finished() {
+        let bonuses = passive_tree.bonuses(&passives.unlocked_nodes);
         cooldowns.nova = Timer::from_seconds(
             match player.class {
                 PlayerClass::Mage => 3.0,
                 PlayerClass::Tank => 8.0,
                 _ => 5.0,
-            },
+            } * bonuses.cooldown_mult(),
             TimerMode::Once,
         );
+        health.pay_life_cost(bonuses.skill_life_cost);
         match player.class {
             PlayerClass::Tank => {
                 commands
@@ -369,35 +418,35 @@ pub fn player_skills(
                     let angle = (i as f32) * 0.2;
                     let shoot_dir = Quat::from_rotation_z(angle) * (-direction).extend(0.0);
                     let shoot_dir_2d = shoot_dir.truncate();
-                    commands.spawn((
-                        SpriteBundle {
-                            texture: arrow_texture.clone(),
-                            sprite: Sprite {
-                                color: Color::srgb(1.0, 1.0, 0.5),
-                                custom_size: Some(Vec2::new(24.0, 24.0)),
+                    let arrow = commands
+                        .spawn((
+                            SpriteBundle {
+                                texture: arrow_texture.clone(),
+                                sprite: Sprite {
+                                    color: Color::srgb(1.0, 1.0, 0.5),
+                                    custom_size: Some(Vec2::new(24.0, 24.0)),
+                                    ..default()
+                                },
+                                transform: Transform::from_translation(player_pos.extend(5.0))
+                                    .with_rotation(Quat::from_rotation_z(
+                                        shoot_dir_2d.y.atan2(shoot_dir_2d.x),
+                                    )),
                                 ..default()
                             },
-                            transform: Transform::from_translation(player_pos.extend(5.0))
-                                .with_rotation(Quat::from_rotation_z(
-                                    shoot_dir_2d.y.atan2(shoot_dir_2d.x),
-                                )),
-                            ..default()
-                        },
-                        Projectile {
-                            damage: stats.damage * 0.8,
-                            owner: player_entity,
-                            pierce: 0,
-                            chain_count: if passives.unlocked_nodes.contains(&10) {
-                                1
-                            } else {
-                                0
+                            Projectile {
+                                damage: stats.damage * 0.8,
+                                owner: player_entity,
+                                pierce: 0,
+                                hit_entities: HashSet::new(),
+                                crit_bonus: VOLLEY_CRIT_BONUS,
+                                kind: AttackKind::Skill,
+                                spent: false,
                             },
-                            hit_entities: HashSet::new(),
-                            is_crit: false,
-                        },
-                        Velocity(shoot_dir_2d * 700.0),
-                        Lifetime(Timer::from_seconds(1.0, TimerMode::Once)),
-                    ));
+                            Velocity(shoot_dir_2d * 700.0),
+                            Lifetime(Timer::from_seconds(1.0, TimerMode::Once)),
+                        ))
+                        .id();
+                    attach_projectile_behaviors(&mut commands, arrow, &bonuses);
                 }
             }
             PlayerClass::Mage => {
@@ -455,6 +504,7 @@ pub fn spawn_player(
     let mut skill_cooldowns = SkillCooldowns {
         dash: Timer::from_seconds(DASH_COOLDOWN, TimerMode::Once),
         nova: Timer::from_seconds(NOVA_COOLDOWN_DEFAULT, TimerMode::Once),
+        special: Timer::from_seconds(0.0, TimerMode::Once),
     };
     match class {
         PlayerClass::Tank => {
@@ -462,6 +512,11 @@ pub fn spawn_player(
             health.current = 200.0;
             shield.amount = 50.0;
             stats.armor = 50.0;
+            stats.fire_resistance = 0.2;
+            stats.cold_resistance = 0.2;
+            stats.lightning_resistance = 0.2;
+            stats.block_chance = TANK_BLOCK_CHANCE;
+            stats.block_amount = TANK_BLOCK_AMOUNT;
             stats.speed = 170.0;
             stats.damage = 30.0;
             stats.life_regen = 5.0;
@@ -472,6 +527,7 @@ pub fn spawn_player(
             stats.speed = 240.0;
             stats.attack_speed = 1.6;
             stats.damage = 18.0;
+            stats.evasion = ARCHER_EVASION;
             attack_cooldown = Timer::from_seconds(0.15, TimerMode::Once);
         }
         PlayerClass::Mage => {
@@ -505,7 +561,9 @@ pub fn spawn_player(
             AttackCooldown(attack_cooldown),
             skill_cooldowns,
             PlayerPassives::default(),
+            ElementalStatus::default(),
             CharacterState::Idle,
+            MeleeCombo::default(),
             SpriteBundle {
                 texture: sprites.soldier_idle.clone(),
                 sprite: Sprite {
@@ -526,6 +584,7 @@ pub fn spawn_player(
                 state: CharacterState::Idle,
             },
         ))
+        .insert((BaseStats(stats), MaxLifeScale(1.0)))
         .id();
     commands.entity(player_entity).with_children(|parent| {
         parent.spawn((
@@ -567,3 +626,67 @@ pub fn check_player_death(
         next_state.set(GameState::GameOver);
     }
 }
+
+pub fn update_player_ailments(
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ElementalStatus), With<Player>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let Ok((player_entity, mut status)) = query.get_single_mut() else {
+        return;
+    };
+
+    status.tick(time.delta());
+    if status.burn_timer.just_finished() && status.fire_stacks > 0 {
+        damage_events.send(DamageEvent {
+            target: player_entity,
+            attacker: None,
+            amount: PLAYER_BURN_DAMAGE_PER_STACK * status.fire_stacks as f32,
+            damage_type: DamageType::Fire,
+            source: DamageSource::DoT,
+            kind: AttackKind::Burn,
+            crit_bonus: 0.0,
+        });
+    }
+}
+
+pub fn recompute_player_stats(
+    passive_tree: Res<PassiveTree>,
+    mut lost_buffs: RemovedComponents<PetBuffed>,
+    mut players: Query<(
+        Entity,
+        Ref<BaseStats>,
+        Ref<PlayerPassives>,
+        Option<Ref<PetBuffed>>,
+        &mut Stats,
+        &mut Health,
+        &mut MaxLifeScale,
+    )>,
+) {
+    let lost_buffs: HashSet<Entity> = lost_buffs.read().collect();
+    for (entity, base, passives, buffed, mut stats, mut health, mut life_scale) in
+        players.iter_mut()
+    {
+        let dirty = base.is_changed()
+            || passives.is_changed()
+            || buffed.as_ref().is_some_and(|buffed| buffed.is_added())
+            || lost_buffs.contains(&entity);
+        if !dirty {
+            continue;
+        }
+        *stats = passive_tree.allocated_stats(&base.0, &passives.unlocked_nodes);
+        if buffed.is_some() {
+            stats.armor = stats.armor.max(PET_BUFF_MIN_ARMOR);
+            stats.damage = stats.damage.max(PET_BUFF_MIN_DAMAGE);
+        }
+
+        let scale = passive_tree
+            .bonuses(&passives.unlocked_nodes)
+            .max_life_mult();
+        if scale != life_scale.0 {
+            health.max *= scale / life_scale.0;
+            health.current = health.current.min(health.max);
+            life_scale.0 = scale;
+        }
+    }
+}
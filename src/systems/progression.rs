@@ -2,6 +2,8 @@ use crate::components::*;
 use crate::constants::*;
 use crate::events::*;
 use crate::resources::*;
+use crate::systems::affixes::*;
+use crate::systems::floating_text::queue_floating_text;
 use bevy::prelude::*;
 use rand::Rng;
 
@@ -58,27 +60,14 @@ pub fn spawn_progression_event(
         ProgressionEventType::HordeWave => {
             horde_wave.active = true;
             horde_wave.timer = Some(Timer::from_seconds(HORDE_WAVE_DURATION, TimerMode::Once));
-            commands.spawn((
-                HordeWaveMarker,
-                Text2dBundle {
-                    text: Text::from_section(
-                        "HORDE WAVE!",
-                        TextStyle {
-                            font_size: 48.0,
-                            color: Color::srgb(1.0, 0.3, 0.3),
-                            ..default()
-                        },
-                    ),
-                    transform: Transform::from_translation(
-                        player_pos.extend(100.0) + Vec3::Y * 100.0,
-                    ),
-                    ..default()
-                },
-                DamageNumber {
-                    velocity: Vec2::new(0.0, 30.0),
-                    lifetime: Timer::from_seconds(2.0, TimerMode::Once),
-                },
-            ));
+            queue_floating_text(
+                &mut commands,
+                FloatingTextEvent::announcement(
+                    "HORDE WAVE!",
+                    Color::srgb(1.0, 0.3, 0.3),
+                    player_pos.extend(100.0) + Vec3::Y * 100.0,
+                ),
+            );
         }
         ProgressionEventType::TreasureGoblin => {
             spawn_treasure_goblin(&mut commands, &sprites, player_pos, level, &mut rng);
@@ -118,6 +107,7 @@ fn spawn_elite(
     let base_xp = (50.0 * ELITE_XP_MULTIPLIER) as u32;
 
     let size = Vec2::new(280.0, 280.0);
+    let affixes = roll_elite_affixes(rng, map_tier.0);
 
     let elite_entity = commands
         .spawn((
@@ -128,7 +118,7 @@ fn spawn_elite(
                 damage: base_damage,
                 xp_value: base_xp,
                 attack_cooldown: Timer::from_seconds(0.8, TimerMode::Once),
-                speed: 70.0,
+                speed: 70.0 * affix_speed_mult(&affixes),
             },
             ElementalStatus::default(),
             Health {
@@ -186,24 +176,24 @@ fn spawn_elite(
         ));
     });
 
-    commands.spawn((
-        Text2dBundle {
-            text: Text::from_section(
-                "ELITE!",
-                TextStyle {
-                    font_size: 36.0,
-                    color: Color::srgb(0.8, 0.4, 1.0),
-                    ..default()
-                },
-            ),
-            transform: Transform::from_translation(spawn_pos.extend(100.0) + Vec3::Y * 80.0),
-            ..default()
-        },
-        DamageNumber {
-            velocity: Vec2::new(0.0, 40.0),
-            lifetime: Timer::from_seconds(1.5, TimerMode::Once),
-        },
-    ));
+    attach_elite_affixes(
+        commands,
+        elite_entity,
+        &affixes,
+        base_damage,
+        ELITE_AURA_RADIUS,
+        size.y / 2.0 + 35.0,
+    );
+
+    queue_floating_text(
+        commands,
+        FloatingTextEvent::label(
+            FloatingTextStyle::Callout,
+            "ELITE!",
+            Color::srgb(0.8, 0.4, 1.0),
+            spawn_pos.extend(100.0) + Vec3::Y * 80.0,
+        ),
+    );
 }
 
 fn spawn_treasure_goblin(
@@ -290,24 +280,15 @@ fn spawn_treasure_goblin(
         ));
     });
 
-    commands.spawn((
-        Text2dBundle {
-            text: Text::from_section(
-                "TREASURE GOBLIN!",
-                TextStyle {
-                    font_size: 32.0,
-                    color: Color::srgb(1.0, 0.85, 0.0),
-                    ..default()
-                },
-            ),
-            transform: Transform::from_translation(spawn_pos.extend(100.0) + Vec3::Y * 60.0),
-            ..default()
-        },
-        DamageNumber {
-            velocity: Vec2::new(0.0, 30.0),
-            lifetime: Timer::from_seconds(1.5, TimerMode::Once),
-        },
-    ));
+    queue_floating_text(
+        commands,
+        FloatingTextEvent::label(
+            FloatingTextStyle::Callout,
+            "TREASURE GOBLIN!",
+            Color::srgb(1.0, 0.85, 0.0),
+            spawn_pos.extend(100.0) + Vec3::Y * 60.0,
+        ),
+    );
 }
 
 fn spawn_shrine(commands: &mut Commands, player_pos: Vec2, rng: &mut impl Rng) {
@@ -315,11 +296,13 @@ fn spawn_shrine(commands: &mut Commands, player_pos: Vec2, rng: &mut impl Rng) {
     let distance = rng.gen_range(150.0..350.0);
     let spawn_pos = player_pos + Vec2::from_angle(angle) * distance;
 
-    let shrine_type = match rng.gen_range(0..4) {
+    let shrine_type = match rng.gen_range(0..6) {
         0 => ShrineType::Damage,
         1 => ShrineType::Speed,
         2 => ShrineType::Defense,
-        _ => ShrineType::CritChance,
+        3 => ShrineType::Evasion,
+        4 => ShrineType::CritChance,
+        _ => ShrineType::Respec,
     };
 
     let color = match shrine_type {
@@ -327,6 +310,8 @@ fn spawn_shrine(commands: &mut Commands, player_pos: Vec2, rng: &mut impl Rng) {
         ShrineType::Speed => Color::srgb(0.3, 1.0, 0.3),
         ShrineType::Defense => Color::srgb(0.3, 0.5, 1.0),
         ShrineType::CritChance => Color::srgb(1.0, 1.0, 0.3),
+        ShrineType::Evasion => Color::srgb(0.6, 1.0, 0.9),
+        ShrineType::Respec => Color::srgb(0.8, 0.5, 1.0),
     };
 
     commands.spawn((
@@ -350,26 +335,19 @@ fn spawn_shrine(commands: &mut Commands, player_pos: Vec2, rng: &mut impl Rng) {
         ShrineType::Speed => "SPEED SHRINE",
         ShrineType::Defense => "DEFENSE SHRINE",
         ShrineType::CritChance => "CRIT SHRINE",
+        ShrineType::Evasion => "EVASION SHRINE",
+        ShrineType::Respec => "RESPEC SHRINE",
     };
 
-    commands.spawn((
-        Text2dBundle {
-            text: Text::from_section(
-                shrine_name,
-                TextStyle {
-                    font_size: 24.0,
-                    color,
-                    ..default()
-                },
-            ),
-            transform: Transform::from_translation(spawn_pos.extend(100.0) + Vec3::Y * 50.0),
-            ..default()
-        },
-        DamageNumber {
-            velocity: Vec2::new(0.0, 20.0),
-            lifetime: Timer::from_seconds(2.0, TimerMode::Once),
-        },
-    ));
+    queue_floating_text(
+        commands,
+        FloatingTextEvent::label(
+            FloatingTextStyle::Notice,
+            shrine_name,
+            color,
+            spawn_pos.extend(100.0) + Vec3::Y * 50.0,
+        ),
+    );
 }
 
 fn spawn_challenge_zone(commands: &mut Commands, player_pos: Vec2, rng: &mut impl Rng) {
@@ -395,24 +373,15 @@ fn spawn_challenge_zone(commands: &mut Commands, player_pos: Vec2, rng: &mut imp
         },
     ));
 
-    commands.spawn((
-        Text2dBundle {
-            text: Text::from_section(
-                "CHALLENGE ZONE!",
-                TextStyle {
-                    font_size: 28.0,
-                    color: Color::srgb(0.2, 0.8, 1.0),
-                    ..default()
-                },
-            ),
-            transform: Transform::from_translation(spawn_pos.extend(100.0) + Vec3::Y * 80.0),
-            ..default()
-        },
-        DamageNumber {
-            velocity: Vec2::new(0.0, 25.0),
-            lifetime: Timer::from_seconds(2.0, TimerMode::Once),
-        },
-    ));
+    queue_floating_text(
+        commands,
+        FloatingTextEvent::label(
+            FloatingTextStyle::Notice,
+            "CHALLENGE ZONE!",
+            Color::srgb(0.2, 0.8, 1.0),
+            spawn_pos.extend(100.0) + Vec3::Y * 80.0,
+        ),
+    );
 }
 
 pub fn update_horde_wave(time: Res<Time>, mut horde_wave: ResMut<HordeWaveActive>) {
@@ -534,10 +503,11 @@ pub fn follow_elite_aura(
 
 pub fn interact_with_shrine(
     mut commands: Commands,
-    player_query: Query<(Entity, &Transform), With<Player>>,
+    mut player_query: Query<(Entity, &Transform, &mut PlayerPassives), With<Player>>,
     mut shrines: Query<(Entity, &Transform, &mut Shrine, &mut Sprite)>,
+    mut sound_events: EventWriter<SoundEvent>,
 ) {
-    let Ok((player_entity, player_transform)) = player_query.get_single() else {
+    let Ok((player_entity, player_transform, mut passives)) = player_query.get_single_mut() else {
         return;
     };
 
@@ -552,39 +522,38 @@ pub fn interact_with_shrine(
         if shrine_pos.distance(player_pos) < 50.0 {
             shrine.used = true;
             sprite.color = Color::srgba(0.5, 0.5, 0.5, 0.5);
-
-            commands.entity(player_entity).insert(ShrineBuff {
-                buff_type: shrine.buff_type,
-                timer: Timer::from_seconds(SHRINE_BUFF_DURATION, TimerMode::Once),
+            sound_events.send(SoundEvent {
+                kind: SoundKind::Shrine,
+                position: Some(shrine_pos),
             });
 
+            if shrine.buff_type == ShrineType::Respec {
+                passives.respec_charges += 1;
+            } else {
+                commands.entity(player_entity).insert(ShrineBuff {
+                    buff_type: shrine.buff_type,
+                    timer: Timer::from_seconds(SHRINE_BUFF_DURATION, TimerMode::Once),
+                });
+            }
+
             let buff_name = match shrine.buff_type {
                 ShrineType::Damage => "+50% DAMAGE",
                 ShrineType::Speed => "+30% SPEED",
                 ShrineType::Defense => "+50 ARMOR",
                 ShrineType::CritChance => "+25% CRIT",
+                ShrineType::Evasion => "+20% EVASION",
+                ShrineType::Respec => "+1 RESPEC",
             };
 
-            commands.spawn((
-                Text2dBundle {
-                    text: Text::from_section(
-                        buff_name,
-                        TextStyle {
-                            font_size: 28.0,
-                            color: Color::srgb(1.0, 1.0, 1.0),
-                            ..default()
-                        },
-                    ),
-                    transform: Transform::from_translation(
-                        player_transform.translation + Vec3::new(0.0, 80.0, 100.0),
-                    ),
-                    ..default()
-                },
-                DamageNumber {
-                    velocity: Vec2::new(0.0, 50.0),
-                    lifetime: Timer::from_seconds(1.5, TimerMode::Once),
-                },
-            ));
+            queue_floating_text(
+                &mut commands,
+                FloatingTextEvent::label(
+                    FloatingTextStyle::Notice,
+                    buff_name,
+                    Color::srgb(1.0, 1.0, 1.0),
+                    player_transform.translation + Vec3::new(0.0, 80.0, 100.0),
+                ),
+            );
 
             commands
                 .entity(shrine_entity)
@@ -691,15 +660,17 @@ pub fn spawn_mini_boss(
     let damage_scale = 1.0 + (level.level as f32 - 1.0) * LEVEL_DAMAGE_SCALE;
 
     let size = Vec2::splat(280.0);
+    let affixes = roll_elite_affixes(&mut rng, map_tier.0);
+    let damage = 40.0 * tier_scale * damage_scale;
 
     let mini_boss_entity = commands
         .spawn((
             MiniBoss,
             Enemy {
-                damage: 40.0 * tier_scale * damage_scale,
+                damage,
                 xp_value: 300 * map_tier.0,
                 attack_cooldown: Timer::from_seconds(0.7, TimerMode::Once),
-                speed: 100.0 + (map_tier.0 as f32 * 5.0),
+                speed: (100.0 + (map_tier.0 as f32 * 5.0)) * affix_speed_mult(&affixes),
             },
             ElementalStatus::default(),
             Health {
@@ -757,24 +728,24 @@ pub fn spawn_mini_boss(
         ));
     });
 
-    commands.spawn((
-        Text2dBundle {
-            text: Text::from_section(
-                "MINI-BOSS!",
-                TextStyle {
-                    font_size: 40.0,
-                    color: Color::srgb(0.9, 0.3, 0.3),
-                    ..default()
-                },
-            ),
-            transform: Transform::from_translation(spawn_pos.extend(100.0) + Vec3::Y * 100.0),
-            ..default()
-        },
-        DamageNumber {
-            velocity: Vec2::new(0.0, 40.0),
-            lifetime: Timer::from_seconds(2.0, TimerMode::Once),
-        },
-    ));
+    attach_elite_affixes(
+        &mut commands,
+        mini_boss_entity,
+        &affixes,
+        damage,
+        ELITE_AURA_RADIUS,
+        size.y / 2.0 + 35.0,
+    );
+
+    queue_floating_text(
+        &mut commands,
+        FloatingTextEvent::label(
+            FloatingTextStyle::Callout,
+            "MINI-BOSS!",
+            Color::srgb(0.9, 0.3, 0.3),
+            spawn_pos.extend(100.0) + Vec3::Y * 100.0,
+        ),
+    );
 }
 
 pub fn boss_entrance_effect(
@@ -783,14 +754,25 @@ pub fn boss_entrance_effect(
     mut boss_entrance_active: ResMut<BossEntranceActive>,
     mut entrances: Query<(Entity, &mut BossEntrance)>,
     mut darkens: Query<(Entity, &mut Sprite, &ScreenDarken)>,
+    mut sound_events: EventWriter<SoundEvent>,
 ) {
     for (entity, mut entrance) in entrances.iter_mut() {
+        if entrance.is_added() {
+            sound_events.send(SoundEvent {
+                kind: SoundKind::BossDarkening,
+                position: None,
+            });
+        }
         entrance.timer.tick(time.delta());
 
         match entrance.phase {
             BossEntrancePhase::Darkening => {
                 if entrance.timer.fraction() > 0.5 {
                     entrance.phase = BossEntrancePhase::Spawning;
+                    sound_events.send(SoundEvent {
+                        kind: SoundKind::BossSpawn,
+                        position: None,
+                    });
                 }
                 for (_, mut sprite, _) in darkens.iter_mut() {
                     sprite.color = Color::srgba(0.0, 0.0, 0.0, entrance.timer.fraction() * 0.6);
@@ -867,6 +849,7 @@ pub fn spawn_boss_with_entrance(
     boss_query: Query<&Boss>,
     mut boss_entrance_active: ResMut<BossEntranceActive>,
     entrance_query: Query<&BossEntrance>,
+    roster: Res<BossRoster>,
 ) {
     let spawn_interval = BOSS_SPAWN_INTERVAL;
     let current_time = game_stats.time_survived;
@@ -885,6 +868,10 @@ pub fn spawn_boss_with_entrance(
         return;
     }
 
+    let Some(definition) = roster.for_tier(map_tier.0) else {
+        return;
+    };
+
     boss_entrance_active.0 = true;
 
     commands.spawn((
@@ -907,27 +894,30 @@ pub fn spawn_boss_with_entrance(
 
     let spawn_pos = Vec2::ZERO;
     let tier_scale = 1.0 + (map_tier.0 as f32 - 1.0) * 0.5;
+    let damage = definition.damage * tier_scale;
+    let speed = definition.speed + (map_tier.0 as f32 * 8.0);
 
     commands.spawn((
         Boss,
+        BossBrain::new(definition.clone(), damage, speed),
         Enemy {
-            damage: 80.0 * tier_scale,
+            damage,
             xp_value: 1000 * map_tier.0,
             attack_cooldown: Timer::from_seconds(0.6, TimerMode::Once),
-            speed: 140.0 + (map_tier.0 as f32 * 8.0),
+            speed,
         },
         ElementalStatus::default(),
         Health {
-            current: 8000.0 * tier_scale,
-            max: 8000.0 * tier_scale,
+            current: definition.health * tier_scale,
+            max: definition.health * tier_scale,
         },
         Velocity(Vec2::ZERO),
         CharacterState::Idle,
         SpriteBundle {
             texture: sprites.orc_idle.clone(),
             sprite: Sprite {
-                color: Color::srgb(0.5, 0.1, 0.1),
-                custom_size: Some(Vec2::splat(400.0)),
+                color: definition.color,
+                custom_size: Some(Vec2::splat(definition.size)),
                 ..default()
             },
             transform: Transform::from_translation(spawn_pos.extend(6.0)),
@@ -944,24 +934,14 @@ pub fn spawn_boss_with_entrance(
         },
     ));
 
-    commands.spawn((
-        Text2dBundle {
-            text: Text::from_section(
-                "BOSS INCOMING!",
-                TextStyle {
-                    font_size: 56.0,
-                    color: Color::srgb(1.0, 0.2, 0.2),
-                    ..default()
-                },
-            ),
-            transform: Transform::from_translation(Vec3::new(0.0, 150.0, 100.0)),
-            ..default()
-        },
-        DamageNumber {
-            velocity: Vec2::new(0.0, 20.0),
-            lifetime: Timer::from_seconds(2.5, TimerMode::Once),
-        },
-    ));
+    queue_floating_text(
+        &mut commands,
+        FloatingTextEvent::announcement(
+            "BOSS INCOMING!",
+            Color::srgb(1.0, 0.2, 0.2),
+            Vec3::new(0.0, 150.0, 100.0),
+        ),
+    );
 }
 
 pub fn handle_boss_death(
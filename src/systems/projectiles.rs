@@ -0,0 +1,321 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::helpers::*;
+use crate::resources::*;
+use bevy::prelude::*;
+use std::collections::HashSet;
+use std::f32::consts::{PI, TAU};
+
+pub fn attach_projectile_behaviors(
+    commands: &mut Commands,
+    projectile: Entity,
+    bonuses: &PassiveBonuses,
+) {
+    let mut entity_commands = commands.entity(projectile);
+    if bonuses.ricochet > 0 {
+        entity_commands.insert(Chain(bonuses.ricochet));
+    }
+    if bonuses.homing {
+        entity_commands.insert(Homing {
+            turn_rate: HOMING_TURN_RATE,
+            range: HOMING_RANGE,
+        });
+    }
+    if bonuses.split_on_hit {
+        entity_commands.insert(SplitOnHit(SPLIT_COUNT));
+    }
+    if bonuses.fork_on_hit {
+        entity_commands.insert(ForkOnHit);
+    }
+    if bonuses.returning {
+        entity_commands.insert(Returning {
+            max_distance: RETURN_DISTANCE,
+            travelled: 0.0,
+            returning: false,
+        });
+    }
+}
+
+pub fn spawn_orbiting_projectile(
+    commands: &mut Commands,
+    owner: Entity,
+    owner_pos: Vec2,
+    damage: f32,
+    angle: f32,
+) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgb(0.7, 0.9, 1.0),
+                custom_size: Some(Vec2::splat(20.0)),
+                ..default()
+            },
+            transform: Transform::from_translation(
+                (owner_pos + Vec2::from_angle(angle) * ORBIT_RADIUS).extend(5.0),
+            ),
+            ..default()
+        },
+        Projectile {
+            damage,
+            owner,
+            pierce: u32::MAX,
+            hit_entities: HashSet::new(),
+            crit_bonus: 0.0,
+            kind: AttackKind::Primary,
+            spent: false,
+        },
+        Orbit {
+            angle,
+            radius: ORBIT_RADIUS,
+            angular_speed: ORBIT_ANGULAR_SPEED,
+            rehit: Timer::from_seconds(ORBIT_REHIT_INTERVAL, TimerMode::Repeating),
+        },
+        Velocity(Vec2::ZERO),
+        Lifetime(Timer::from_seconds(ORBIT_LIFETIME, TimerMode::Once)),
+    ));
+}
+
+fn spawn_child_projectile(
+    commands: &mut Commands,
+    parent: &Projectile,
+    sprite: &Sprite,
+    texture: &Handle<Image>,
+    position: Vec2,
+    velocity: Vec2,
+    exclude: Entity,
+) {
+    commands.spawn((
+        SpriteBundle {
+            texture: texture.clone(),
+            sprite: sprite.clone(),
+            transform: Transform::from_translation(position.extend(5.0))
+                .with_rotation(Quat::from_rotation_z(velocity.y.atan2(velocity.x))),
+            ..default()
+        },
+        Projectile {
+            damage: parent.damage,
+            owner: parent.owner,
+            pierce: 0,
+            hit_entities: HashSet::from([exclude]),
+            crit_bonus: parent.crit_bonus,
+            kind: parent.kind,
+            spent: false,
+        },
+        Velocity(velocity),
+        Lifetime(Timer::from_seconds(
+            PROJECTILE_LIFETIME * 0.5,
+            TimerMode::Once,
+        )),
+    ));
+}
+
+pub fn steer_homing_projectiles(
+    time: Res<Time>,
+    mut projectiles: Query<(&mut Transform, &mut Velocity, &Projectile, &Homing)>,
+    enemies: Query<(Entity, &Transform), (With<Enemy>, Without<Projectile>)>,
+) {
+    for (mut transform, mut velocity, projectile, homing) in projectiles.iter_mut() {
+        let pos = transform.translation.truncate();
+        let target = enemies
+            .iter()
+            .filter(|(entity, _)| !projectile.hit_entities.contains(entity))
+            .map(|(_, t)| t.translation.truncate())
+            .filter(|target| in_range(*target, pos, homing.range))
+            .min_by(|a, b| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)));
+        let Some(target) = target else {
+            continue;
+        };
+
+        let speed = velocity.0.length();
+        let current = velocity.0.y.atan2(velocity.0.x);
+        let to_target = target - pos;
+        let desired = to_target.y.atan2(to_target.x);
+        let diff = (desired - current + PI).rem_euclid(TAU) - PI;
+        let max_turn = homing.turn_rate * time.delta_seconds();
+        let angle = current + diff.clamp(-max_turn, max_turn);
+
+        velocity.0 = Vec2::from_angle(angle) * speed;
+        transform.rotation = Quat::from_rotation_z(angle);
+    }
+}
+
+pub fn update_orbiting_projectiles(
+    time: Res<Time>,
+    mut orbiters: Query<(&mut Transform, &mut Projectile, &mut Orbit)>,
+    owners: Query<&Transform, Without<Projectile>>,
+) {
+    for (mut transform, mut projectile, mut orbit) in orbiters.iter_mut() {
+        let Ok(owner_transform) = owners.get(projectile.owner) else {
+            projectile.spent = true;
+            continue;
+        };
+        orbit.angle = (orbit.angle + orbit.angular_speed * time.delta_seconds()) % TAU;
+        let offset = Vec2::from_angle(orbit.angle) * orbit.radius;
+        transform.translation =
+            (owner_transform.translation.truncate() + offset).extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(orbit.angle + PI / 2.0);
+
+        orbit.rehit.tick(time.delta());
+        if orbit.rehit.just_finished() {
+            projectile.hit_entities.clear();
+        }
+    }
+}
+
+pub fn update_returning_projectiles(
+    time: Res<Time>,
+    mut projectiles: Query<(
+        &mut Transform,
+        &mut Velocity,
+        &mut Projectile,
+        &mut Returning,
+    )>,
+    owners: Query<&Transform, Without<Projectile>>,
+) {
+    for (mut transform, mut velocity, mut projectile, mut returning) in projectiles.iter_mut() {
+        // Returning projectiles pass through everything until they are caught.
+        projectile.spent = false;
+        let speed = velocity.0.length();
+        returning.travelled += speed * time.delta_seconds();
+
+        if !returning.returning {
+            if returning.travelled >= returning.max_distance {
+                returning.returning = true;
+                projectile.hit_entities.clear();
+            }
+            continue;
+        }
+
+        let Ok(owner_transform) = owners.get(projectile.owner) else {
+            projectile.spent = true;
+            continue;
+        };
+        let to_owner = owner_transform.translation.truncate() - transform.translation.truncate();
+        if to_owner.length() < RETURN_CATCH_RADIUS {
+            projectile.spent = true;
+            continue;
+        }
+        let direction = to_owner.normalize_or_zero();
+        velocity.0 = direction * speed;
+        transform.rotation = Quat::from_rotation_z(direction.y.atan2(direction.x));
+    }
+}
+
+pub fn chain_projectiles(
+    mut hit_events: EventReader<ProjectileHitEvent>,
+    mut projectiles: Query<(&mut Transform, &mut Velocity, &mut Projectile, &mut Chain)>,
+    enemies: Query<(Entity, &Transform), (With<Enemy>, Without<Projectile>)>,
+) {
+    for hit in hit_events.read() {
+        let Ok((mut transform, mut velocity, mut projectile, mut chain)) =
+            projectiles.get_mut(hit.projectile)
+        else {
+            continue;
+        };
+        if !projectile.spent || chain.0 == 0 {
+            continue;
+        }
+
+        let next = enemies
+            .iter()
+            .filter(|(entity, _)| !projectile.hit_entities.contains(entity))
+            .map(|(_, t)| t.translation.truncate())
+            .filter(|pos| in_range(*pos, hit.position, CHAIN_SEARCH_RADIUS))
+            .min_by(|a, b| {
+                a.distance_squared(hit.position)
+                    .total_cmp(&b.distance_squared(hit.position))
+            });
+        let Some(target_pos) = next else {
+            continue;
+        };
+
+        let new_dir = (target_pos - hit.position).normalize_or_zero();
+        velocity.0 = new_dir * velocity.0.length();
+        transform.rotation = Quat::from_rotation_z(new_dir.y.atan2(new_dir.x));
+        chain.0 -= 1;
+        projectile.spent = false;
+    }
+}
+
+pub fn fork_projectiles(
+    mut commands: Commands,
+    mut hit_events: EventReader<ProjectileHitEvent>,
+    projectiles: Query<(&Velocity, &Projectile, &Sprite, &Handle<Image>), With<ForkOnHit>>,
+) {
+    for hit in hit_events.read() {
+        let Ok((velocity, projectile, sprite, texture)) = projectiles.get(hit.projectile) else {
+            continue;
+        };
+        commands.entity(hit.projectile).remove::<ForkOnHit>();
+
+        for angle in [-FORK_ANGLE, FORK_ANGLE] {
+            spawn_child_projectile(
+                &mut commands,
+                projectile,
+                sprite,
+                texture,
+                hit.position,
+                Vec2::from_angle(angle).rotate(velocity.0),
+                hit.target,
+            );
+        }
+    }
+}
+
+pub fn split_projectiles(
+    mut commands: Commands,
+    mut hit_events: EventReader<ProjectileHitEvent>,
+    projectiles: Query<(&Velocity, &Projectile, &Sprite, &Handle<Image>, &SplitOnHit)>,
+    enemies: Query<(Entity, &Transform), (With<Enemy>, Without<Projectile>)>,
+) {
+    for hit in hit_events.read() {
+        let Ok((velocity, projectile, sprite, texture, split)) = projectiles.get(hit.projectile)
+        else {
+            continue;
+        };
+        commands.entity(hit.projectile).remove::<SplitOnHit>();
+
+        let mut targets: Vec<Vec2> = enemies
+            .iter()
+            .filter(|(entity, _)| *entity != hit.target)
+            .map(|(_, t)| t.translation.truncate())
+            .filter(|pos| in_range(*pos, hit.position, HOMING_RANGE))
+            .collect();
+        targets.sort_by(|a, b| {
+            a.distance_squared(hit.position)
+                .total_cmp(&b.distance_squared(hit.position))
+        });
+
+        let speed = velocity.0.length();
+        for i in 0..split.0 {
+            let direction = targets
+                .get(i as usize)
+                .map(|target| (*target - hit.position).normalize_or_zero())
+                .unwrap_or_else(|| {
+                    let spread = TAU * (i + 1) as f32 / (split.0 + 1) as f32;
+                    Vec2::from_angle(spread).rotate(velocity.0.normalize_or_zero())
+                });
+            spawn_child_projectile(
+                &mut commands,
+                projectile,
+                sprite,
+                texture,
+                hit.position,
+                direction * speed,
+                hit.target,
+            );
+        }
+    }
+}
+
+pub fn despawn_spent_projectiles(
+    mut commands: Commands,
+    projectiles: Query<(Entity, &Projectile)>,
+) {
+    for (entity, projectile) in projectiles.iter() {
+        if projectile.spent {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -1,6 +1,10 @@
+use bevy::input::keyboard::KeyboardInput;
 use bevy::prelude::*;
 
 use crate::components::*;
+use crate::constants::DEATH_RECAP_TOP_SOURCES;
+use crate::events::*;
+use crate::helpers::*;
 use crate::plugins::ui::HudRoot;
 use crate::resources::*;
 use crate::systems::pets::spawn_pet;
@@ -75,9 +79,159 @@ pub fn setup_class_selection(mut commands: Commands) {
                         });
                     }
                 });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(420.0),
+                            margin: UiRect::top(Val::Px(40.0)),
+                            padding: UiRect::axes(Val::Px(10.0), Val::Px(6.0)),
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        background_color: Color::srgb(0.1, 0.1, 0.13).into(),
+                        border_color: Color::srgb(0.4, 0.4, 0.5).into(),
+                        ..default()
+                    },
+                    BuildCodeInput::default(),
+                ))
+                .with_children(|input| {
+                    input.spawn((
+                        TextBundle::from_section(
+                            "Click to enter a build code",
+                            TextStyle {
+                                font_size: 18.0,
+                                color: Color::srgb(0.5, 0.5, 0.55),
+                                ..default()
+                            },
+                        ),
+                        BuildCodeInputText,
+                    ));
+                });
+
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 16.0,
+                        color: Color::srgb(0.7, 0.7, 0.7),
+                        ..default()
+                    },
+                ),
+                BuildCodeStatus,
+            ));
         });
 }
 
+pub fn handle_build_code_input(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut pending: ResMut<PendingSelection>,
+    mut input_query: Query<(
+        Entity,
+        &Interaction,
+        &mut BuildCodeInput,
+        &mut BorderColor,
+        Option<&mut ClipboardPaste>,
+    )>,
+    mut input_text: Query<&mut Text, (With<BuildCodeInputText>, Without<BuildCodeStatus>)>,
+    mut status_text: Query<&mut Text, (With<BuildCodeStatus>, Without<BuildCodeInputText>)>,
+) {
+    let Ok((entity, interaction, mut input, mut border, paste_task)) = input_query.get_single_mut()
+    else {
+        return;
+    };
+    if *interaction == Interaction::Pressed {
+        input.focused = true;
+    }
+    if keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::Escape) {
+        input.focused = false;
+    }
+    let paste = keyboard.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]) && keyboard.just_pressed(KeyCode::KeyV);
+
+    let pasted = paste_task.and_then(|mut task| poll_clipboard(&mut task.0));
+    if pasted.is_some() {
+        commands.entity(entity).remove::<ClipboardPaste>();
+    }
+
+    let edited = if let Some(clipboard) = pasted {
+        keyboard_events.clear();
+        match clipboard {
+            Some(text) => {
+                input.text = text.chars().take(64).collect();
+                true
+            }
+            None => {
+                if let Ok(mut status) = status_text.get_single_mut() {
+                    let section = &mut status.sections[0];
+                    section.value = "Could not read the clipboard".to_string();
+                    section.style.color = Color::srgb(1.0, 0.4, 0.4);
+                }
+                false
+            }
+        }
+    } else if !input.focused {
+        keyboard_events.clear();
+        false
+    } else if paste {
+        // Swallow the 'v' keystroke so it isn't typed after the pasted code.
+        keyboard_events.clear();
+        commands
+            .entity(entity)
+            .insert(ClipboardPaste(read_clipboard()));
+        false
+    } else {
+        read_text_input(&mut keyboard_events, &mut input.text, 64)
+    };
+
+    if edited {
+        pending.build = BuildCode::decode(&input.text);
+        if let Ok(mut status) = status_text.get_single_mut() {
+            let section = &mut status.sections[0];
+            (section.value, section.style.color) = match &pending.build {
+                Some(build) => (
+                    format!(
+                        "Loaded {:?} build ({} nodes) - planned when you play {:?}",
+                        build.class,
+                        build.nodes.len(),
+                        build.class
+                    ),
+                    Color::srgb(0.4, 1.0, 0.4),
+                ),
+                None if input.text.is_empty() => (String::new(), Color::WHITE),
+                None => ("Invalid build code".to_string(), Color::srgb(1.0, 0.4, 0.4)),
+            };
+        }
+    }
+
+    *border = if input.focused {
+        Color::srgb(0.9, 0.85, 0.5).into()
+    } else {
+        Color::srgb(0.4, 0.4, 0.5).into()
+    };
+    if let Ok(mut text) = input_text.get_single_mut() {
+        let section = &mut text.sections[0];
+        if input.text.is_empty() && !input.focused {
+            section.value = "Click to enter a build code".to_string();
+            section.style.color = Color::srgb(0.5, 0.5, 0.55);
+        } else {
+            section.value = if input.focused {
+                format!("{}|", input.text)
+            } else {
+                input.text.clone()
+            };
+            section.style.color = Color::WHITE;
+        }
+    }
+}
+
 pub fn handle_class_selection(
     mut interaction_query: Query<
         (&Interaction, &ClassButton, &mut BackgroundColor),
@@ -236,6 +390,12 @@ pub fn start_game(
 
     let class = pending.class.unwrap_or(PlayerClass::Archer);
     let player_entity = spawn_player(&mut commands, &sprites, Vec3::ZERO, class);
+    if let Some(build) = pending.build.as_ref().filter(|build| build.class == class) {
+        commands.entity(player_entity).insert(PlayerPassives {
+            planned: build.nodes.clone(),
+            ..default()
+        });
+    }
 
     if class == PlayerClass::Tamer {
         for (i, &pet_type) in pending.pets.iter().enumerate() {
@@ -248,7 +408,42 @@ pub fn start_game(
     }
 }
 
-pub fn show_game_over(mut commands: Commands, game_stats: Res<GameStats>) {
+pub fn show_game_over(
+    mut commands: Commands,
+    game_stats: Res<GameStats>,
+    combat_log: Res<CombatLog>,
+    death_recap: Res<DeathRecap>,
+) {
+    let breakdown = AttackKind::PLAYER_SOURCES
+        .iter()
+        .filter_map(|kind| {
+            let dealt = combat_log.dealt_by_kind.get(kind).copied().unwrap_or(0.0);
+            (dealt > 0.0).then(|| format!("  {}: {:.0}\n", kind.name(), dealt))
+        })
+        .collect::<String>();
+
+    let mut recap = String::from("-- Death Recap --\n");
+    if let Some(blow) = death_recap.killing_blow() {
+        let tag = if blow.is_boss {
+            " [Boss]"
+        } else if blow.is_elite {
+            " [Elite]"
+        } else {
+            ""
+        };
+        recap.push_str(&format!(
+            "Killed by {}{}: {:.0} {:?} ({:.0} before mitigation)\n",
+            blow.attacker, tag, blow.mitigated, blow.damage_type, blow.raw
+        ));
+    }
+    for (attacker, total, hits) in death_recap.top_sources(DEATH_RECAP_TOP_SOURCES) {
+        recap.push_str(&format!("  {}: {:.0} in {} hits\n", attacker, total, hits));
+    }
+    recap.push_str(&format!(
+        "Shield absorbed: {:.0}\n\n",
+        death_recap.shield_absorbed()
+    ));
+
     commands.spawn((
         GameOverUi,
         TextBundle::from_sections([
@@ -262,8 +457,12 @@ pub fn show_game_over(mut commands: Commands, game_stats: Res<GameStats>) {
             ),
             TextSection::new(
                 format!(
-                    "Time: {:.1}s\nEnemies: {}\nDamage: {:.0}\n\n",
-                    game_stats.time_survived, game_stats.enemies_killed, game_stats.damage_dealt
+                    "Time: {:.1}s\nEnemies: {}\nDamage Dealt: {:.0}\n{}Damage Taken: {:.0}\n\n",
+                    game_stats.time_survived,
+                    game_stats.enemies_killed,
+                    game_stats.damage_dealt,
+                    breakdown,
+                    game_stats.damage_taken
                 ),
                 TextStyle {
                     font_size: 28.0,
@@ -271,6 +470,14 @@ pub fn show_game_over(mut commands: Commands, game_stats: Res<GameStats>) {
                     ..default()
                 },
             ),
+            TextSection::new(
+                recap,
+                TextStyle {
+                    font_size: 20.0,
+                    color: Color::srgb(0.9, 0.6, 0.5),
+                    ..default()
+                },
+            ),
             TextSection::new(
                 "Press R to Restart",
                 TextStyle {
@@ -282,7 +489,7 @@ pub fn show_game_over(mut commands: Commands, game_stats: Res<GameStats>) {
         ])
         .with_style(Style {
             position_type: PositionType::Absolute,
-            top: Val::Percent(30.0),
+            top: Val::Percent(15.0),
             left: Val::Percent(35.0),
             ..default()
         }),
@@ -295,22 +502,63 @@ pub fn restart_game(
     state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
     mut game_stats: ResMut<GameStats>,
+    mut combat_log: ResMut<CombatLog>,
+    mut death_recap: ResMut<DeathRecap>,
     mut pending: ResMut<PendingSelection>,
-    all_entities: Query<Entity, (Without<Camera2d>, Without<Window>, Without<HudRoot>)>,
+    mut level_up: ResMut<LevelUpOffers>,
+    mut restart_events: EventReader<RestartRunEvent>,
+    all_entities: Query<
+        Entity,
+        (
+            Without<Camera2d>,
+            Without<Window>,
+            Without<HudRoot>,
+            Without<MusicLayer>,
+            Without<PauseMenuUi>,
+            Without<Parent>,
+        ),
+    >,
     game_over_ui: Query<Entity, With<GameOverUi>>,
 ) {
-    if *state.get() == GameState::GameOver && keyboard.just_pressed(KeyCode::KeyR) {
-        *game_stats = GameStats::default();
-        *pending = PendingSelection::default();
-
-        for entity in game_over_ui.iter() {
-            commands.entity(entity).despawn_recursive();
+    let requested = restart_events
+        .read()
+        .last()
+        .map(|event| event.keep_selection);
+    let keep_selection = match requested {
+        Some(keep_selection) => keep_selection,
+        None if *state.get() == GameState::GameOver && keyboard.just_pressed(KeyCode::KeyR) => {
+            false
         }
+        None => return,
+    };
 
-        for entity in all_entities.iter() {
-            commands.entity(entity).despawn_recursive();
-        }
+    *game_stats = GameStats::default();
+    combat_log.clear();
+    death_recap.hits.clear();
+    *level_up = LevelUpOffers::default();
+    // MapData's default seed never matches a tier, so the next run regenerates its map.
+    commands.insert_resource(MapTier(1));
+    commands.insert_resource(MapData::default());
+    commands.insert_resource(HordeWaveActive::default());
+    commands.insert_resource(BossEntranceActive::default());
+    commands.insert_resource(BossDeathActive::default());
+    commands.insert_resource(ProgressionEventTimer::default());
+    commands.insert_resource(MiniBossTimer::new());
+    if !keep_selection {
+        *pending = PendingSelection::default();
+    }
 
-        next_state.set(GameState::CharacterSelection);
+    for entity in game_over_ui.iter() {
+        commands.entity(entity).despawn_recursive();
     }
+
+    for entity in all_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    next_state.set(if keep_selection {
+        GameState::Playing
+    } else {
+        GameState::CharacterSelection
+    });
 }
@@ -0,0 +1,322 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::helpers::*;
+use crate::resources::*;
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+use std::collections::HashSet;
+
+const CLASS_SKILL_KEY: KeyCode = KeyCode::KeyE;
+
+fn ground_target(player_pos: Vec2, cursor: Vec2) -> Vec2 {
+    let offset = (cursor - player_pos).clamp_length_max(GROUND_SKILL_RANGE);
+    clamp_to_bounds(player_pos + offset, MAP_BOUNDS)
+}
+
+fn ground_skill_radius(skill: ClassSkill) -> f32 {
+    match skill {
+        ClassSkill::ArrowRain => ARROW_RAIN_RADIUS,
+        _ => METEOR_RADIUS,
+    }
+}
+
+pub fn class_skill_input(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    cursor_pos: Res<CursorWorldPos>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Stats,
+            &Player,
+            &PlayerPassives,
+            &mut SkillCooldowns,
+            &mut Health,
+            Has<Dash>,
+        ),
+        With<Player>,
+    >,
+    passive_tree: Res<PassiveTree>,
+    indicators: Query<Entity, Or<(With<GroundTargetRange>, With<GroundTargetReticle>)>>,
+    channels: Query<(), With<Channeled>>,
+    mut sound_events: EventWriter<SoundEvent>,
+) {
+    let Ok((player_entity, transform, stats, player, passives, mut cooldowns, mut health, dashing)) =
+        player_query.get_single_mut()
+    else {
+        return;
+    };
+    cooldowns.special.tick(time.delta());
+    let skill = ClassSkill::for_class(player.class);
+    let bonuses = passive_tree.bonuses(&passives.unlocked_nodes);
+    let cooldown = skill.cooldown() * bonuses.cooldown_mult();
+    let size_mult = 1.0 + bonuses.aoe_size;
+    let player_pos = transform.translation.truncate();
+
+    if skill.is_channeled() {
+        if keyboard.just_pressed(CLASS_SKILL_KEY)
+            && cooldowns.special.finished()
+            && channels.is_empty()
+            && !dashing
+        {
+            cooldowns.special = Timer::from_seconds(cooldown, TimerMode::Once);
+            health.pay_life_cost(bonuses.skill_life_cost);
+            sound_events.send(SoundEvent {
+                kind: SoundKind::SkillCast,
+                position: Some(player_pos),
+            });
+            spawn_channel(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                skill,
+                player_entity,
+                player_pos,
+                cursor_pos.0,
+                stats.damage,
+                size_mult,
+            );
+        }
+        return;
+    }
+
+    let aiming = !indicators.is_empty();
+    if !aiming {
+        if keyboard.just_pressed(CLASS_SKILL_KEY) && cooldowns.special.finished() {
+            let radius = ground_skill_radius(skill) * size_mult;
+            commands.spawn((
+                MaterialMesh2dBundle {
+                    mesh: meshes
+                        .add(Annulus::new(GROUND_SKILL_RANGE - 3.0, GROUND_SKILL_RANGE))
+                        .into(),
+                    material: materials.add(Color::srgba(1.0, 1.0, 1.0, 0.25)),
+                    transform: Transform::from_translation(player_pos.extend(2.0)),
+                    ..default()
+                },
+                GroundTargetRange,
+            ));
+            commands.spawn((
+                MaterialMesh2dBundle {
+                    mesh: meshes.add(Circle::new(radius)).into(),
+                    material: materials.add(Color::srgba(1.0, 0.9, 0.3, 0.2)),
+                    transform: Transform::from_translation(
+                        ground_target(player_pos, cursor_pos.0).extend(2.0),
+                    ),
+                    ..default()
+                },
+                GroundTargetReticle,
+            ));
+        }
+        return;
+    }
+
+    // Dashing while aiming throws the cast away. Checking `pressed` rather than
+    // `just_released` also catches a release that landed before the indicators spawned.
+    if dashing || !keyboard.pressed(CLASS_SKILL_KEY) {
+        for entity in indicators.iter() {
+            commands.entity(entity).despawn();
+        }
+        if dashing {
+            return;
+        }
+        cooldowns.special = Timer::from_seconds(cooldown, TimerMode::Once);
+        health.pay_life_cost(bonuses.skill_life_cost);
+        sound_events.send(SoundEvent {
+            kind: SoundKind::SkillCast,
+            position: Some(player_pos),
+        });
+        let target = ground_target(player_pos, cursor_pos.0);
+        let (damage_type, damage_mult, color, tick, duration) = match skill {
+            ClassSkill::ArrowRain => (
+                DamageType::Physical,
+                ARROW_RAIN_DAMAGE_MULT,
+                Color::srgba(1.0, 1.0, 0.5, 0.45),
+                0.2,
+                2.0,
+            ),
+            _ => (
+                DamageType::Fire,
+                METEOR_DAMAGE_MULT,
+                Color::srgba(1.0, 0.35, 0.0, 0.45),
+                0.25,
+                1.0,
+            ),
+        };
+        let radius = ground_skill_radius(skill) * size_mult;
+        commands.spawn((
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Circle::new(radius)).into(),
+                material: materials.add(color),
+                transform: Transform::from_translation(target.extend(3.0)),
+                ..default()
+            },
+            AoeShape::circle(radius),
+            AoeEffect {
+                damage: stats.damage * damage_mult,
+                damage_type,
+                crit_bonus: 0.0,
+                owner: player_entity,
+                tick_timer: Timer::from_seconds(tick, TimerMode::Repeating),
+                duration: Timer::from_seconds(duration, TimerMode::Once),
+                hit_this_tick: HashSet::new(),
+                kind: AttackKind::Skill,
+            },
+        ));
+    }
+}
+
+fn spawn_channel(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    skill: ClassSkill,
+    owner: Entity,
+    owner_pos: Vec2,
+    cursor: Vec2,
+    damage: f32,
+    size_mult: f32,
+) {
+    let aoe = |damage_type, damage_mult, tick| AoeEffect {
+        damage: damage * damage_mult,
+        damage_type,
+        crit_bonus: 0.0,
+        owner,
+        tick_timer: Timer::from_seconds(tick, TimerMode::Repeating),
+        duration: Timer::from_seconds(CHANNEL_MAX_DURATION, TimerMode::Once),
+        hit_this_tick: HashSet::new(),
+        kind: AttackKind::Skill,
+    };
+    let channeled = Channeled {
+        skill,
+        key: CLASS_SKILL_KEY,
+    };
+
+    if skill == ClassSkill::Whirlwind {
+        let radius = WHIRLWIND_RADIUS * size_mult;
+        commands.spawn((
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Circle::new(radius)).into(),
+                material: materials.add(Color::srgba(0.6, 0.7, 1.0, 0.35)),
+                transform: Transform::from_translation(owner_pos.extend(3.0)),
+                ..default()
+            },
+            AoeShape::circle(radius),
+            aoe(DamageType::Physical, WHIRLWIND_DAMAGE_MULT, 0.25),
+            channeled,
+        ));
+        return;
+    }
+
+    let direction = (cursor - owner_pos).normalize_or(Vec2::X);
+    let half_extents = Vec2::new(SPIRIT_BEAM_LENGTH, SPIRIT_BEAM_WIDTH) * size_mult / 2.0;
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(0.4, 1.0, 0.6, 0.5),
+                custom_size: Some(half_extents * 2.0),
+                ..default()
+            },
+            transform: Transform::from_translation(
+                (owner_pos + direction * half_extents.x).extend(3.0),
+            )
+            .with_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))),
+            ..default()
+        },
+        aoe(DamageType::Lightning, SPIRIT_BEAM_DAMAGE_MULT, 0.15),
+        AoeShape {
+            shape: MeleeShape::Rect { half_extents },
+            direction,
+        },
+        channeled,
+    ));
+}
+
+pub fn update_ground_target_indicators(
+    cursor_pos: Res<CursorWorldPos>,
+    player_query: Query<&Transform, With<Player>>,
+    mut ranges: Query<&mut Transform, (With<GroundTargetRange>, Without<Player>)>,
+    mut reticles: Query<
+        &mut Transform,
+        (
+            With<GroundTargetReticle>,
+            Without<Player>,
+            Without<GroundTargetRange>,
+        ),
+    >,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+    for mut transform in ranges.iter_mut() {
+        transform.translation = player_pos.extend(transform.translation.z);
+    }
+    let target = ground_target(player_pos, cursor_pos.0);
+    for mut transform in reticles.iter_mut() {
+        transform.translation = target.extend(transform.translation.z);
+    }
+}
+
+/// Leaving `Playing` mid-aim cancels the cast so the indicators don't linger.
+pub fn clear_ground_target_indicators(
+    mut commands: Commands,
+    indicators: Query<Entity, Or<(With<GroundTargetRange>, With<GroundTargetReticle>)>>,
+) {
+    for entity in indicators.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn update_channeled_skills(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    cursor_pos: Res<CursorWorldPos>,
+    owners: Query<(&Transform, Has<Dash>), With<Player>>,
+    mut channels: Query<
+        (
+            Entity,
+            &Channeled,
+            &AoeEffect,
+            &mut Transform,
+            Option<&mut AoeShape>,
+        ),
+        Without<Player>,
+    >,
+) {
+    for (entity, channeled, aoe, mut transform, shape) in channels.iter_mut() {
+        let Ok((owner_transform, dashing)) = owners.get(aoe.owner) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        if dashing || !keyboard.pressed(channeled.key) {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let owner_pos = owner_transform.translation.truncate();
+        match channeled.skill {
+            ClassSkill::SpiritBeam => {
+                let Some(mut shape) = shape else {
+                    continue;
+                };
+                let direction = (cursor_pos.0 - owner_pos).normalize_or(shape.direction);
+                let reach = match shape.shape {
+                    MeleeShape::Rect { half_extents } => half_extents.x,
+                    MeleeShape::Arc { radius, .. } => radius,
+                };
+                shape.direction = direction;
+                transform.translation =
+                    (owner_pos + direction * reach).extend(transform.translation.z);
+                transform.rotation = Quat::from_rotation_z(direction.y.atan2(direction.x));
+            }
+            _ => {
+                transform.translation = owner_pos.extend(transform.translation.z);
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::components::*;
+use crate::constants::*;
 use crate::plugins::ui::*;
 use crate::resources::*;
 use bevy::prelude::*;
@@ -6,15 +7,18 @@ use bevy::prelude::*;
 // === BOSS HEALTH BAR ===
 pub fn spawn_boss_health_bar(
     mut commands: Commands,
-    boss_query: Query<Entity, Added<Boss>>,
+    boss_query: Query<Option<&BossBrain>, Added<Boss>>,
     existing_bar: Query<Entity, With<BossHealthBarUi>>,
 ) {
-    if boss_query.is_empty() {
+    let Some(brain) = boss_query.iter().next() else {
         return;
-    }
+    };
     if !existing_bar.is_empty() {
         return;
     }
+    let title = brain
+        .map(|b| b.definition.name.to_uppercase())
+        .unwrap_or_else(|| "BOSS".to_string());
 
     commands
         .spawn((
@@ -43,7 +47,7 @@ pub fn spawn_boss_health_bar(
         ))
         .with_children(|parent| {
             parent.spawn(TextBundle::from_section(
-                "BOSS",
+                title,
                 TextStyle {
                     font_size: 16.0,
                     color: Color::srgb(1.0, 0.3, 0.3),
@@ -243,6 +247,10 @@ pub fn update_skill_cooldowns_ui(
         let (timer, max_duration) = match overlay.skill_type {
             SkillType::Dash => (&cooldowns.dash, crate::constants::DASH_COOLDOWN),
             SkillType::Nova => (&cooldowns.nova, crate::constants::NOVA_COOLDOWN_DEFAULT),
+            SkillType::Special => (
+                &cooldowns.special,
+                cooldowns.special.duration().as_secs_f32(),
+            ),
         };
 
         if timer.finished() {
@@ -257,6 +265,7 @@ pub fn update_skill_cooldowns_ui(
         let timer = match cooldown_text.skill_type {
             SkillType::Dash => &cooldowns.dash,
             SkillType::Nova => &cooldowns.nova,
+            SkillType::Special => &cooldowns.special,
         };
 
         if timer.finished() {
@@ -286,6 +295,63 @@ pub fn toggle_stats_panel(
     }
 }
 
+// === DPS METER ===
+pub fn toggle_dps_meter(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut game_stats: ResMut<GameStats>,
+    mut panel: Query<&mut Visibility, With<DpsMeterRoot>>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyM) {
+        game_stats.show_dps_meter = !game_stats.show_dps_meter;
+    }
+
+    // Derived from the flag every frame so a restart resetting `GameStats` can't desync it.
+    let target = if game_stats.show_dps_meter {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for mut visibility in panel.iter_mut() {
+        visibility.set_if_neq(target);
+    }
+}
+
+pub fn update_dps_meter(
+    time: Res<Time>,
+    game_stats: Res<GameStats>,
+    combat_log: Res<CombatLog>,
+    mut content: Query<&mut Text, With<DpsMeterContent>>,
+) {
+    if !game_stats.show_dps_meter {
+        return;
+    }
+    let Ok(mut text) = content.get_single_mut() else {
+        return;
+    };
+
+    let breakdown = combat_log.dps_by_kind(time.elapsed_seconds(), DPS_METER_WINDOW);
+    let total: f32 = breakdown.iter().map(|(_, dps)| dps).sum();
+    let mut value = format!("DPS: {:.0}\n", total);
+    for (kind, dps) in breakdown {
+        let share = if total > 0.0 {
+            dps / total * 100.0
+        } else {
+            0.0
+        };
+        value.push_str(&format!(
+            "{:<10}{:>6.0} ({:.0}%)\n",
+            kind.name(),
+            dps,
+            share
+        ));
+    }
+    value.push_str(&format!(
+        "Dealt: {:.0}  Taken: {:.0}",
+        game_stats.damage_dealt, game_stats.damage_taken
+    ));
+    text.sections[0].value = value;
+}
+
 // === STATS PANEL CONTENT ===
 pub fn update_stats_panel(
     player: Query<(&Stats, &PlayerPassives), With<Player>>,
@@ -318,11 +384,15 @@ pub fn update_stats_panel(
          \n-- Defense --\n\
          Armor: {:.0}\n\
          Life Regen: {:.1}/s\n\
+         Resists: {:.0}% / {:.0}% / {:.0}%\n\
+         Evasion: {:.0}%\n\
+         Block: {:.0}% ({:.0}% blocked)\n\
          \n-- Utility --\n\
          Movement Speed: {:.0}\n\
          \n-- Session --\n\
          Kills: {}\n\
          Total Damage: {:.0}\n\
+         Damage Taken: {:.0}\n\
          Time: {:.0}s\n\
          \n-- Passives --\n\
          Unlocked: {}",
@@ -333,9 +403,16 @@ pub fn update_stats_panel(
         stats.crit_multiplier,
         stats.armor,
         stats.life_regen,
+        stats.fire_resistance * 100.0,
+        stats.cold_resistance * 100.0,
+        stats.lightning_resistance * 100.0,
+        stats.evade_chance(0.0) * 100.0,
+        stats.capped_block_chance() * 100.0,
+        stats.block_amount * 100.0,
         stats.speed,
         game_stats.enemies_killed,
         game_stats.damage_dealt,
+        game_stats.damage_taken,
         game_stats.time_survived,
         passives.unlocked_nodes.len(),
     );
@@ -344,11 +421,11 @@ pub fn update_stats_panel(
 // === BUFF/DEBUFF DISPLAY ===
 pub fn update_buff_display(
     mut commands: Commands,
-    player: Query<(Entity, &Shield, Option<&Invulnerable>), With<Player>>,
+    player: Query<(Entity, &Shield, Option<&Invulnerable>, &ElementalStatus), With<Player>>,
     buff_container: Query<Entity, With<BuffContainer>>,
     existing_buffs: Query<(Entity, &BuffIcon)>,
 ) {
-    let Ok((_player_entity, shield, invulnerable)) = player.get_single() else {
+    let Ok((_player_entity, shield, invulnerable, status)) = player.get_single() else {
         return;
     };
 
@@ -356,50 +433,47 @@ pub fn update_buff_display(
         return;
     };
 
-    let has_shield = shield.amount > 0.0;
-    let has_invuln = invulnerable.is_some();
-
-    let mut shield_icon_exists = false;
-    let mut invuln_icon_exists = false;
-
-    for (entity, buff) in existing_buffs.iter() {
-        match buff.buff_type {
-            BuffType::Shield => {
-                if !has_shield {
-                    commands.entity(entity).despawn_recursive();
-                } else {
-                    shield_icon_exists = true;
-                }
-            }
-            BuffType::Invulnerable => {
-                if !has_invuln {
-                    commands.entity(entity).despawn_recursive();
-                } else {
-                    invuln_icon_exists = true;
-                }
-            }
-            _ => {}
-        }
-    }
-
-    if has_shield && !shield_icon_exists {
-        spawn_buff_icon(
-            &mut commands,
-            container_entity,
-            BuffType::Shield,
-            "S",
-            SHIELD_BAR_FILL,
-        );
-    }
-
-    if has_invuln && !invuln_icon_exists {
-        spawn_buff_icon(
-            &mut commands,
-            container_entity,
+    let buffs = [
+        (BuffType::Shield, shield.amount > 0.0, "S", SHIELD_BAR_FILL),
+        (
             BuffType::Invulnerable,
+            invulnerable.is_some(),
             "I",
             Color::srgb(1.0, 0.9, 0.3),
-        );
+        ),
+        (
+            BuffType::Ignited,
+            status.fire_stacks > 0,
+            "IGN",
+            Color::srgb(1.0, 0.45, 0.1),
+        ),
+        (
+            BuffType::Chilled,
+            status.ice_stacks > 0,
+            "CHL",
+            Color::srgb(0.5, 0.85, 1.0),
+        ),
+        (
+            BuffType::Shocked,
+            status.lightning_stacks > 0,
+            "SHK",
+            Color::srgb(1.0, 1.0, 0.4),
+        ),
+    ];
+
+    for (buff_type, active, label, color) in buffs {
+        let existing = existing_buffs
+            .iter()
+            .find(|(_, icon)| icon.buff_type == buff_type)
+            .map(|(entity, _)| entity);
+
+        match (active, existing) {
+            (true, None) => {
+                spawn_buff_icon(&mut commands, container_entity, buff_type, label, color)
+            }
+            (false, Some(entity)) => commands.entity(entity).despawn_recursive(),
+            _ => {}
+        }
     }
 }
 
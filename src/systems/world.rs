@@ -1,9 +1,12 @@
 use crate::components::*;
 use crate::constants::*;
 use crate::events::*;
+use crate::helpers::*;
 use crate::resources::*;
+use bevy::sprite::MaterialMesh2dBundle;
 use bevy::{prelude::*, window::PrimaryWindow};
 use rand::Rng;
+use std::collections::HashSet;
 
 pub fn update_cursor_world_pos(
     mut cursor_pos: ResMut<CursorWorldPos>,
@@ -48,13 +51,18 @@ pub fn collect_xp(
     time: Res<Time>,
     player_query: Query<(&Transform, Entity), With<Player>>,
     mut xp_orbs: Query<(Entity, &mut Transform, &XpOrb, &mut Lifetime), Without<Player>>,
-    mut levels: Query<(&mut Level, &mut Stats, &mut Health, &mut PlayerPassives)>,
+    mut levels: Query<(&mut Level, &mut BaseStats, &mut Health, &mut PlayerPassives)>,
     mut camera_shake: Query<&mut CameraShake, With<Camera2d>>,
+    mut level_up: ResMut<LevelUpOffers>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut sound_events: EventWriter<SoundEvent>,
+    mut text_events: EventWriter<FloatingTextEvent>,
 ) {
     let Ok((player_transform, player_entity)) = player_query.get_single() else {
         return;
     };
-    let Ok((mut level, mut stats, mut health, mut passives)) = levels.get_mut(player_entity) else {
+    let Ok((mut level, mut base_stats, mut health, mut passives)) = levels.get_mut(player_entity)
+    else {
         return;
     };
 
@@ -78,7 +86,13 @@ pub fn collect_xp(
         }
 
         if distance < XP_PICKUP_RADIUS {
+            sound_events.send(SoundEvent {
+                kind: SoundKind::Pickup,
+                position: Some(orb_pos),
+            });
             if level.add_xp(xp_orb.value) {
+                // Baseline growth; the level-up screen adds a chosen upgrade on top.
+                let stats = &mut base_stats.0;
                 stats.damage *= 1.12;
                 stats.speed *= 1.02;
                 stats.attack_speed *= 1.03;
@@ -90,30 +104,22 @@ pub fn collect_xp(
                 health.current = health.max;
 
                 passives.points += 1;
+                level_up.pending += 1;
+                next_state.set(GameState::LevelUp);
+                sound_events.send(SoundEvent {
+                    kind: SoundKind::LevelUp,
+                    position: None,
+                });
 
                 if let Ok(mut shake) = camera_shake.get_single_mut() {
                     crate::plugins::game_feel::add_trauma(&mut shake, 0.3);
                 }
 
-                commands.spawn((
-                    Text2dBundle {
-                        text: Text::from_section(
-                            format!("LEVEL {}!", level.level),
-                            TextStyle {
-                                font_size: 32.0,
-                                color: Color::srgb(1.0, 0.9, 0.2),
-                                ..default()
-                            },
-                        ),
-                        transform: Transform::from_translation(
-                            player_transform.translation + Vec3::new(0.0, 60.0, 100.0),
-                        ),
-                        ..default()
-                    },
-                    DamageNumber {
-                        velocity: Vec2::new(0.0, 80.0),
-                        lifetime: Timer::from_seconds(1.5, TimerMode::Once),
-                    },
+                text_events.send(FloatingTextEvent::label(
+                    FloatingTextStyle::Callout,
+                    format!("LEVEL {}!", level.level),
+                    Color::srgb(1.0, 0.9, 0.2),
+                    player_transform.translation + Vec3::new(0.0, 60.0, 100.0),
                 ));
 
                 commands.spawn((
@@ -200,23 +206,80 @@ pub fn spawn_boss(
 }
 
 pub fn update_hazards(
-    _commands: Commands,
-    _time: Res<Time>,
-    _player_query: Query<(Entity, &Transform), (With<Player>, Without<Hazard>)>,
-    _hazards: Query<
-        (Entity, &mut Transform, &Hazard, &mut Sprite),
-        (With<Hazard>, Without<Player>),
+    mut commands: Commands,
+    time: Res<Time>,
+    targets: Query<
+        (Entity, &Transform, Option<&HazardSlow>),
+        (Or<(With<Player>, With<Enemy>)>, Without<Hazard>),
     >,
-    _damage_events: EventWriter<DamageEvent>,
-    _status_events: EventWriter<ApplyStatusEvent>,
+    mut hazards: Query<(&Transform, &mut Hazard, &Handle<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
 ) {
+    let mut slowed = HashSet::new();
+
+    for (hazard_transform, mut hazard, material) in hazards.iter_mut() {
+        hazard.tick.tick(time.delta());
+        let center = hazard_transform.translation.truncate();
+
+        if hazard.kind == HazardKind::LightningPylon
+            && let Some(material) = materials.get_mut(material)
+        {
+            let charge = hazard.tick.fraction();
+            material.color.set_alpha(0.1 + charge * charge * 0.5);
+        }
+
+        for (entity, transform, _) in targets.iter() {
+            if !in_range(transform.translation.truncate(), center, hazard.radius) {
+                continue;
+            }
+            if hazard.kind == HazardKind::IcePatch {
+                slowed.insert(entity);
+            }
+            if !hazard.tick.just_finished() {
+                continue;
+            }
+            if hazard.damage > 0.0 {
+                damage_events.send(DamageEvent {
+                    target: entity,
+                    attacker: None,
+                    amount: hazard.damage,
+                    damage_type: hazard.damage_type(),
+                    source: DamageSource::DoT,
+                    kind: AttackKind::Hazard,
+                    crit_bonus: 0.0,
+                });
+            }
+            if let Some(effect) = hazard.effect {
+                status_events.send(ApplyStatusEvent {
+                    target: entity,
+                    effect,
+                    applier: None,
+                });
+            }
+        }
+    }
+
+    for (entity, _, slow) in targets.iter() {
+        if slowed.contains(&entity) {
+            if slow.is_none() {
+                commands
+                    .entity(entity)
+                    .try_insert(HazardSlow(ICE_PATCH_SLOW));
+            }
+        } else if slow.is_some() {
+            commands.entity(entity).remove::<HazardSlow>();
+        }
+    }
 }
 
 pub fn handle_loot(
     mut commands: Commands,
     player_query: Query<&Transform, With<Player>>,
     loot_query: Query<(Entity, &Transform), With<Loot>>,
-    mut stats_query: Query<&mut Stats, With<Player>>,
+    mut stats_query: Query<&mut BaseStats, With<Player>>,
+    mut sound_events: EventWriter<SoundEvent>,
 ) {
     let Ok(player_transform) = player_query.get_single() else {
         return;
@@ -225,10 +288,14 @@ pub fn handle_loot(
 
     for (entity, transform) in loot_query.iter() {
         if transform.translation.truncate().distance(player_pos) < 50.0 {
-            if let Ok(mut stats) = stats_query.get_single_mut() {
-                stats.damage += 2.0;
-                stats.crit_chance += 0.01;
+            if let Ok(mut base_stats) = stats_query.get_single_mut() {
+                base_stats.0.damage += 2.0;
+                base_stats.0.crit_chance += 0.01;
                 commands.entity(entity).despawn();
+                sound_events.send(SoundEvent {
+                    kind: SoundKind::Pickup,
+                    position: Some(transform.translation.truncate()),
+                });
             }
         }
     }
@@ -236,10 +303,13 @@ pub fn handle_loot(
 
 pub fn generate_map(
     mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
     map_tier: Res<MapTier>,
     mut map_data: ResMut<MapData>,
     walls: Query<Entity, With<Wall>>,
     obstacles: Query<Entity, With<Obstacle>>,
+    hazards: Query<Entity, With<Hazard>>,
     player_query: Query<&Transform, With<Player>>,
 ) {
     if map_data.seed == map_tier.0 as u64 {
@@ -253,6 +323,9 @@ pub fn generate_map(
     for entity in obstacles.iter() {
         commands.entity(entity).despawn_recursive();
     }
+    for entity in hazards.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
 
     let bounds = map_data.bounds;
     let mut rng = rand::thread_rng();
@@ -305,6 +378,64 @@ pub fn generate_map(
             },
         ));
     }
+
+    let hazard_count = HAZARD_BASE_COUNT + map_tier.0 * HAZARD_COUNT_PER_TIER;
+    let damage_scale = 1.0 + (map_tier.0 as f32 - 1.0) * HAZARD_TIER_DAMAGE_SCALE;
+    for _ in 0..hazard_count {
+        let pos = Vec2::new(
+            rng.gen_range(-bounds..bounds),
+            rng.gen_range(-bounds..bounds),
+        );
+
+        let kind = match rng.gen_range(0..3) {
+            0 => HazardKind::FirePit,
+            1 => HazardKind::IcePatch,
+            _ => HazardKind::LightningPylon,
+        };
+        let (damage, effect, radius, tick, color) = match kind {
+            HazardKind::FirePit => (
+                FIRE_PIT_DAMAGE,
+                Some(PassiveEffect::ChanceFire(1.0)),
+                FIRE_PIT_RADIUS,
+                FIRE_PIT_TICK,
+                Color::srgba(1.0, 0.35, 0.0, 0.45),
+            ),
+            HazardKind::IcePatch => (
+                0.0,
+                Some(PassiveEffect::ChanceIce(1.0)),
+                ICE_PATCH_RADIUS,
+                ICE_PATCH_TICK,
+                Color::srgba(0.5, 0.85, 1.0, 0.35),
+            ),
+            HazardKind::LightningPylon => (
+                LIGHTNING_PYLON_DAMAGE,
+                Some(PassiveEffect::ChanceLightning(1.0)),
+                LIGHTNING_PYLON_RADIUS,
+                LIGHTNING_PYLON_INTERVAL,
+                Color::srgba(1.0, 1.0, 0.3, 0.1),
+            ),
+        };
+
+        if pos.distance(player_pos) < PLAYER_SAFE_RADIUS + radius {
+            continue;
+        }
+
+        commands.spawn((
+            Hazard {
+                kind,
+                damage: damage * damage_scale,
+                effect,
+                radius,
+                tick: Timer::from_seconds(tick, TimerMode::Repeating),
+            },
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Circle::new(radius)).into(),
+                material: materials.add(color),
+                transform: Transform::from_translation(pos.extend(0.5)),
+                ..default()
+            },
+        ));
+    }
 }
 
 pub fn setup_minimap(mut commands: Commands, query: Query<Entity, With<MinimapUi>>) {